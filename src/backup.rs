@@ -0,0 +1,317 @@
+//! Back up and restore Windows Quick Access items.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::path::Path;
+//! use wincent::{
+//!     backup::restore_backup,
+//!     empty::empty_with_backup,
+//!     QuickAccess, WincentResult,
+//! };
+//!
+//! fn main() -> WincentResult<()> {
+//!     let backup_path = Path::new("quick_access.bak");
+//!
+//!     // Save the recent files, then clear them
+//!     empty_with_backup(QuickAccess::RecentFiles, backup_path)?;
+//!
+//!     // Changed your mind? Put them back
+//!     restore_backup(backup_path)?;
+//!
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    error::WincentError,
+    handle::{add_file_to_recent_with_api, ok_if_exists, pin_frequent_folder_with_ps_script},
+    query::{query_pinned_with_ps_script, query_recent_with_ps_script},
+    visible::{is_visialbe_with_registry, set_visiable_with_registry},
+    QuickAccess, WincentResult,
+};
+use std::path::Path;
+
+const BACKUP_HEADER: &str = "# wincent quick access backup";
+const RECENT_VISIBLE_KEY: &str = "visible.recent";
+const FREQUENT_VISIBLE_KEY: &str = "visible.frequent";
+pub(crate) const RECENT_TAG: &str = "recent";
+pub(crate) const FREQUENT_TAG: &str = "frequent";
+const PINNED_TAG: &str = "pinned";
+
+/// A restorable snapshot of Quick Access items and visibility settings.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct QuickAccessBackup {
+    /// Paths of the backed up recent files.
+    pub recent_files: Vec<String>,
+    /// Paths of the backed up frequent folders, pinned or listed because they were often
+    /// visited. Kept for reference, only `pinned_folders` are restored.
+    pub frequent_folders: Vec<String>,
+    /// Paths of the backed up pinned folders, in pin order.
+    pub pinned_folders: Vec<String>,
+    /// Whether recent files were visible in Quick Access.
+    pub recent_visible: bool,
+    /// Whether frequent folders were visible in Quick Access.
+    pub frequent_visible: bool,
+}
+
+impl QuickAccessBackup {
+    /// Serializes the backup into its line-based text format.
+    pub(crate) fn to_text(&self) -> String {
+        let mut lines = vec![
+            BACKUP_HEADER.to_string(),
+            format!("{}={}", RECENT_VISIBLE_KEY, u8::from(self.recent_visible)),
+            format!(
+                "{}={}",
+                FREQUENT_VISIBLE_KEY,
+                u8::from(self.frequent_visible)
+            ),
+        ];
+        lines.extend(
+            self.recent_files
+                .iter()
+                .map(|path| format!("{}\t{}", RECENT_TAG, path)),
+        );
+        lines.extend(
+            self.frequent_folders
+                .iter()
+                .map(|path| format!("{}\t{}", FREQUENT_TAG, path)),
+        );
+        lines.extend(
+            self.pinned_folders
+                .iter()
+                .map(|path| format!("{}\t{}", PINNED_TAG, path)),
+        );
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Parses a backup from its line-based text format.
+    pub(crate) fn from_text(text: &str) -> WincentResult<Self> {
+        let mut backup = QuickAccessBackup {
+            recent_visible: true,
+            frequent_visible: true,
+            ..Default::default()
+        };
+
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            if line.starts_with('#') {
+                continue;
+            }
+
+            if let Some((tag, path)) = line.split_once('\t') {
+                match tag {
+                    RECENT_TAG => backup.recent_files.push(path.to_string()),
+                    FREQUENT_TAG => backup.frequent_folders.push(path.to_string()),
+                    PINNED_TAG => backup.pinned_folders.push(path.to_string()),
+                    _ => return Err(WincentError::InvalidBackup(line.to_string())),
+                }
+            } else if let Some((key, value)) = line.split_once('=') {
+                let visible = match value {
+                    "0" => false,
+                    "1" => true,
+                    _ => return Err(WincentError::InvalidBackup(line.to_string())),
+                };
+                match key {
+                    RECENT_VISIBLE_KEY => backup.recent_visible = visible,
+                    FREQUENT_VISIBLE_KEY => backup.frequent_visible = visible,
+                    _ => return Err(WincentError::InvalidBackup(line.to_string())),
+                }
+            } else {
+                return Err(WincentError::InvalidBackup(line.to_string()));
+            }
+        }
+
+        Ok(backup)
+    }
+}
+
+/// Captures the current Quick Access items of a category along with the visibility settings.
+pub(crate) fn capture_backup(qa_type: QuickAccess) -> WincentResult<QuickAccessBackup> {
+    let recent_files = match qa_type {
+        QuickAccess::RecentFiles | QuickAccess::All => {
+            query_recent_with_ps_script(QuickAccess::RecentFiles)?
+        }
+        QuickAccess::FrequentFolders => Vec::new(),
+    };
+    let frequent_folders = match qa_type {
+        QuickAccess::FrequentFolders | QuickAccess::All => {
+            query_recent_with_ps_script(QuickAccess::FrequentFolders)?
+        }
+        QuickAccess::RecentFiles => Vec::new(),
+    };
+    let pinned_folders = match qa_type {
+        QuickAccess::FrequentFolders | QuickAccess::All => query_pinned_with_ps_script()?,
+        QuickAccess::RecentFiles => Vec::new(),
+    };
+
+    Ok(QuickAccessBackup {
+        recent_files,
+        frequent_folders,
+        pinned_folders,
+        recent_visible: is_visialbe_with_registry(QuickAccess::RecentFiles)?,
+        frequent_visible: is_visialbe_with_registry(QuickAccess::FrequentFolders)?,
+    })
+}
+
+/****************************************************** Backup Quick Access ******************************************************/
+
+/// Writes a backup of the Quick Access items in a category to a file.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to back up
+/// * `backup_path` - The file the backup is written to
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use wincent::{backup::create_backup, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     create_backup(QuickAccess::All, Path::new("quick_access.bak"))?;
+///     Ok(())
+/// }
+/// ```
+pub fn create_backup(qa_type: QuickAccess, backup_path: &Path) -> WincentResult<()> {
+    let backup = capture_backup(qa_type)?;
+    std::fs::write(backup_path, backup.to_text()).map_err(WincentError::Io)
+}
+
+/// Reads a backup file written by [`create_backup`] or [`crate::empty::empty_with_backup`].
+///
+/// # Arguments
+///
+/// * `backup_path` - The backup file to read
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use wincent::{backup::read_backup, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let backup = read_backup(Path::new("quick_access.bak"))?;
+///     println!("{} recent files backed up", backup.recent_files.len());
+///     Ok(())
+/// }
+/// ```
+pub fn read_backup(backup_path: &Path) -> WincentResult<QuickAccessBackup> {
+    let text = std::fs::read_to_string(backup_path).map_err(WincentError::Io)?;
+    QuickAccessBackup::from_text(&text)
+}
+
+/// Restores Quick Access items and visibility settings from a backup file.
+///
+/// Recent files are added back to the recent list, oldest first so their order is kept,
+/// and pinned folders are pinned again in pin order. Folders that were only listed because
+/// they were often visited are not pinned. Paths that no longer exist on disk are skipped.
+///
+/// # Arguments
+///
+/// * `backup_path` - The backup file to restore from
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use wincent::{backup::restore_backup, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     restore_backup(Path::new("quick_access.bak"))?;
+///     Ok(())
+/// }
+/// ```
+pub fn restore_backup(backup_path: &Path) -> WincentResult<()> {
+    let backup = read_backup(backup_path)?;
+
+    // Recent files are listed most recent first, the last one added comes out on top
+    for file in backup
+        .recent_files
+        .iter()
+        .rev()
+        .filter(|p| Path::new(p).is_file())
+    {
+        add_file_to_recent_with_api(file)?;
+    }
+
+    for folder in backup
+        .pinned_folders
+        .iter()
+        .filter(|p| Path::new(p).is_dir())
+    {
+        ok_if_exists(pin_frequent_folder_with_ps_script(folder))?;
+    }
+
+    set_visiable_with_registry(QuickAccess::RecentFiles, backup.recent_visible)?;
+    set_visiable_with_registry(QuickAccess::FrequentFolders, backup.frequent_visible)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{cleanup_test_env, setup_test_env};
+    use serial_test::serial;
+
+    fn sample_backup() -> QuickAccessBackup {
+        QuickAccessBackup {
+            recent_files: vec![
+                "C:\\Users\\User\\Documents\\report.docx".to_string(),
+                "C:\\Users\\User\\file with spaces.txt".to_string(),
+            ],
+            frequent_folders: vec![
+                "C:\\Projects\\wincent".to_string(),
+                "C:\\Users\\User\\Downloads".to_string(),
+            ],
+            pinned_folders: vec!["C:\\Projects\\wincent".to_string()],
+            recent_visible: false,
+            frequent_visible: true,
+        }
+    }
+
+    #[test]
+    fn test_backup_text_round_trip() -> WincentResult<()> {
+        let backup = sample_backup();
+        let parsed = QuickAccessBackup::from_text(&backup.to_text())?;
+        assert_eq!(parsed, backup);
+        Ok(())
+    }
+
+    #[test]
+    fn test_backup_rejects_invalid_lines() {
+        let invalid = [
+            "unknown\tC:\\path",
+            "visible.recent=2",
+            "visible.other=1",
+            "not a backup line",
+        ];
+
+        for line in invalid {
+            assert!(
+                matches!(
+                    QuickAccessBackup::from_text(line),
+                    Err(WincentError::InvalidBackup(_))
+                ),
+                "Line should be rejected: {}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_read_backup_file() -> WincentResult<()> {
+        let test_dir = setup_test_env()?;
+        let backup_path = test_dir.join("quick_access.bak");
+
+        let backup = sample_backup();
+        std::fs::write(&backup_path, backup.to_text())?;
+        assert_eq!(read_backup(&backup_path)?, backup);
+
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! Configure process-wide behavior of Quick Access operations.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::time::Duration;
+//! use wincent::config::{get_config, set_config};
+//!
+//! // Give freshly created network folders a moment to become visible
+//! let mut config = get_config();
+//! config.network_path_retries = 3;
+//! config.network_path_retry_delay = Duration::from_millis(200);
+//! set_config(config);
+//!
+//! // Run scripts from a fixed folder with an extra environment variable
+//! let mut config = get_config();
+//! config.script_working_dir = Some("C:\\Tools".into());
+//! config.script_env.push(("WINCENT_MODE".to_string(), "batch".to_string()));
+//! set_config(config);
+//! ```
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Process-wide settings applied to all Quick Access operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WincentConfig {
+    /// How many extra times a missing UNC directory is checked before it is reported
+    /// as an invalid path, `0` disables retries. Local paths are never retried.
+    pub network_path_retries: u32,
+    /// Delay between two existence checks of a UNC directory.
+    pub network_path_retry_delay: Duration,
+    /// Whether generated PowerShell scripts start with a UTF-8 byte order mark.
+    ///
+    /// Scripts are always written as UTF-8. Windows PowerShell 5.1 reads `-File` scripts
+    /// without a BOM in the system ANSI code page, so turning this off is only safe for
+    /// ASCII paths or with PowerShell 7 as `powershell`.
+    pub script_bom: bool,
+    /// Whether scripts without parameters are passed inline with `-EncodedCommand`
+    /// instead of being written to a temporary `.ps1` file first.
+    pub encoded_commands: bool,
+    /// Whether query output lines that look neither like a path, a URL nor a virtual
+    /// shell item are dropped, such as banners or warnings PowerShell prints on some systems.
+    pub filter_query_output: bool,
+    /// Working directory of the PowerShell processes, `None` keeps the one of the
+    /// current process.
+    pub script_working_dir: Option<PathBuf>,
+    /// Extra environment variables set for the PowerShell processes, on top of the
+    /// environment inherited from the current process.
+    pub script_env: Vec<(String, String)>,
+    /// Whether PowerShell processes are started without a console window, so GUI
+    /// applications do not flash one on every operation. Turn it off to watch scripts run.
+    pub hide_console_window: bool,
+    /// Name under which the items added by this application are recorded, see
+    /// [`crate::managed`]. `None` shares one record with every application using wincent
+    /// without an `app_id` for the current user.
+    pub app_id: Option<String>,
+}
+
+impl WincentConfig {
+    const DEFAULT: WincentConfig = WincentConfig {
+        network_path_retries: 0,
+        network_path_retry_delay: Duration::from_millis(200),
+        script_bom: true,
+        encoded_commands: true,
+        filter_query_output: true,
+        script_working_dir: None,
+        script_env: Vec::new(),
+        hide_console_window: true,
+        app_id: None,
+    };
+}
+
+impl Default for WincentConfig {
+    fn default() -> Self {
+        WincentConfig::DEFAULT
+    }
+}
+
+static CONFIG: RwLock<WincentConfig> = RwLock::new(WincentConfig::DEFAULT);
+
+/// Gets a copy of the current process-wide configuration.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::config::get_config;
+///
+/// let config = get_config();
+/// println!("Network path retries: {}", config.network_path_retries);
+/// ```
+pub fn get_config() -> WincentConfig {
+    match CONFIG.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    }
+}
+
+/// Replaces the process-wide configuration.
+///
+/// # Arguments
+///
+/// * `config` - The configuration applied to all following operations
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::config::{set_config, WincentConfig};
+///
+/// set_config(WincentConfig {
+///     network_path_retries: 2,
+///     ..Default::default()
+/// });
+/// ```
+pub fn set_config(config: WincentConfig) {
+    let mut guard = match CONFIG.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *guard = config;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_set_get_config() {
+        let initial = get_config();
+
+        let config = WincentConfig {
+            network_path_retries: 3,
+            ..Default::default()
+        };
+        set_config(config.clone());
+        assert_eq!(get_config(), config);
+
+        set_config(initial.clone());
+        assert_eq!(get_config(), initial);
+    }
+
+    #[test]
+    fn test_default_config() {
+        let config = WincentConfig::default();
+        assert_eq!(config.network_path_retries, 0, "Retries should be off");
+        assert!(config.script_bom, "Scripts should keep the UTF-8 BOM");
+        assert!(config.encoded_commands, "Static scripts should run inline");
+        assert!(
+            config.filter_query_output,
+            "Query output should be filtered"
+        );
+        assert!(
+            config.hide_console_window,
+            "Scripts should run without a console window"
+        );
+        assert!(
+            config.script_working_dir.is_none(),
+            "Scripts should inherit the working directory"
+        );
+        assert!(
+            config.script_env.is_empty(),
+            "No extra environment by default"
+        );
+        assert!(
+            config.app_id.is_none(),
+            "The record should be shared by default"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! Register and run custom PowerShell scripts alongside the built-in operations.
+//!
+//! A custom script is registered under a key and generated on every run from an optional
+//! parameter, the same way the built-in scripts are. Registered scripts are run with the
+//! same PowerShell settings as the built-in ones and are reported to metrics sinks under
+//! their key.
+//!
+//! The registry is shared by all threads of the process. Registering, unregistering and
+//! running scripts may happen concurrently; a run uses the strategy registered when it
+//! started, even if the key is replaced while the script is running.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use wincent::{
+//!     custom::{execute_custom_script, register},
+//!     WincentResult,
+//! };
+//!
+//! fn main() -> WincentResult<()> {
+//!     register(
+//!         "ClearThumbnailCache",
+//!         Box::new(|_: Option<&str>| {
+//!             Ok("Remove-Item \"$env:LOCALAPPDATA\\Microsoft\\Windows\\Explorer\\thumbcache_*.db\" -ErrorAction SilentlyContinue".to_string())
+//!         }),
+//!     )?;
+//!
+//!     let output = execute_custom_script("ClearThumbnailCache", None)?;
+//!     println!("Succeeded: {}", output.status.success());
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    error::WincentError, feasible::check_script_feasible, scripts::execute_ps_content,
+    WincentResult,
+};
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+/// Generates the content of a custom PowerShell script.
+///
+/// Implemented for closures taking the optional parameter passed to
+/// [`execute_custom_script`].
+pub trait ScriptStrategy: Send + Sync {
+    /// Returns the script content for the given parameter.
+    fn generate(&self, para: Option<&str>) -> WincentResult<String>;
+}
+
+impl<F> ScriptStrategy for F
+where
+    F: Fn(Option<&str>) -> WincentResult<String> + Send + Sync,
+{
+    fn generate(&self, para: Option<&str>) -> WincentResult<String> {
+        self(para)
+    }
+}
+
+static CUSTOM_SCRIPTS: RwLock<BTreeMap<String, Arc<dyn ScriptStrategy>>> =
+    RwLock::new(BTreeMap::new());
+
+/// Looks up the strategy registered under a key.
+pub(crate) fn get_strategy(key: &str) -> Option<Arc<dyn ScriptStrategy>> {
+    let scripts = match CUSTOM_SCRIPTS.read() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    scripts.get(key).cloned()
+}
+
+/// Registers a custom script under a key, replacing any script registered before.
+///
+/// # Arguments
+///
+/// * `key` - The name of the script, also used as the operation name for metrics
+/// * `strategy` - Generates the script content on each run
+///
+/// # Returns
+///
+/// Returns `true` if a script was already registered under the key and was replaced.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{custom::register, WincentResult};
+///
+/// fn main() -> WincentResult<()> {
+///     register(
+///         "OpenFolder",
+///         Box::new(|para: Option<&str>| {
+///             Ok(format!("Invoke-Item -LiteralPath '{}'", para.unwrap_or(".")))
+///         }),
+///     )?;
+///     Ok(())
+/// }
+/// ```
+pub fn register(key: &str, strategy: Box<dyn ScriptStrategy>) -> WincentResult<bool> {
+    if key.trim().is_empty() {
+        return Err(WincentError::InvalidArgument(
+            "Custom script key cannot be empty".to_string(),
+        ));
+    }
+
+    let mut scripts = match CUSTOM_SCRIPTS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    Ok(scripts
+        .insert(key.to_string(), Arc::from(strategy))
+        .is_some())
+}
+
+/// Removes the custom script registered under a key.
+///
+/// # Arguments
+///
+/// * `key` - The name the script was registered under
+///
+/// # Returns
+///
+/// Returns `true` if a script was registered under the key.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::custom::unregister;
+///
+/// if !unregister("OpenFolder") {
+///     println!("No script named OpenFolder");
+/// }
+/// ```
+pub fn unregister(key: &str) -> bool {
+    let mut scripts = match CUSTOM_SCRIPTS.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    scripts.remove(key).is_some()
+}
+
+/// Lists the keys of all registered custom scripts, sorted.
+pub fn registered_scripts() -> Vec<String> {
+    let scripts = match CUSTOM_SCRIPTS.read() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    scripts.keys().cloned().collect()
+}
+
+/// Runs the custom script registered under a key.
+///
+/// # Arguments
+///
+/// * `key` - The name the script was registered under
+/// * `para` - Optional parameter passed to the script strategy
+///
+/// # Returns
+///
+/// Returns the output of the PowerShell process. A script that ran but failed is
+/// reported through the exit status, not as an error.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{custom::execute_custom_script, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let output = execute_custom_script("OpenFolder", Some("C:\\Projects"))?;
+///     if !output.status.success() {
+///         println!("{}", String::from_utf8_lossy(&output.stderr));
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn execute_custom_script(key: &str, para: Option<&str>) -> WincentResult<std::process::Output> {
+    let strategy = get_strategy(key).ok_or_else(|| {
+        WincentError::InvalidArgument(format!("No custom script registered for key: {}", key))
+    })?;
+
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    let content = strategy.generate(para)?;
+
+    execute_ps_content(key, &content, para.is_none(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_and_unregister() -> WincentResult<()> {
+        let key = "wincent_test_register";
+
+        assert!(!register(
+            key,
+            Box::new(|para: Option<&str>| Ok(format!("Write-Output '{}'", para.unwrap_or(""))))
+        )?);
+        assert!(registered_scripts().contains(&key.to_string()));
+
+        let strategy = get_strategy(key).expect("Script should be registered");
+        assert_eq!(strategy.generate(Some("hello"))?, "Write-Output 'hello'");
+
+        assert!(
+            register(key, Box::new(|_: Option<&str>| Ok(String::new())))?,
+            "Registering the same key again should replace the script"
+        );
+
+        assert!(unregister(key));
+        assert!(!unregister(key));
+        assert!(get_strategy(key).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_register_rejects_empty_key() {
+        let result = register(" ", Box::new(|_: Option<&str>| Ok(String::new())));
+        assert!(matches!(result, Err(WincentError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_execute_unregistered_script() {
+        let result = execute_custom_script("wincent_test_missing", None);
+        assert!(matches!(result, Err(WincentError::InvalidArgument(_))));
+    }
+
+    #[test]
+    #[ignore]
+    fn test_execute_custom_script() -> WincentResult<()> {
+        let key = "wincent_test_execute";
+        register(
+            key,
+            Box::new(|para: Option<&str>| Ok(format!("Write-Output '{}'", para.unwrap_or("none")))),
+        )?;
+
+        let output = execute_custom_script(key, Some("wincent"))?;
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).contains("wincent"));
+
+        unregister(key);
+        Ok(())
+    }
+}
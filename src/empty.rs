@@ -37,72 +37,133 @@
 //! ```
 
 use crate::{
-    error::WincentError, feasible::check_script_feasible,
-    handle::unpin_frequent_folder_with_ps_script, query::query_recent_with_ps_script, QuickAccess,
-    WincentResult,
+    backup::capture_backup,
+    error::WincentError,
+    feasible::check_script_feasible,
+    handle::{
+        ensure_pinunpin_feasible, ok_if_missing, remove_recent_files_with_ps_script, repin_folders,
+        unpin_frequent_folder_with_ps_script,
+    },
+    query::{
+        list_shortcuts, query_pinned_with_ps_script, query_recent_with_ps_script,
+        validate_jumplist, JumplistHealth, TYPED_PATHS_KEY,
+    },
+    utils::{
+        get_quick_access_data_files, get_windows_recent_folder, lock_mutations, paths_equal,
+        ComGuard,
+    },
+    QuickAccess, WincentResult,
 };
-use std::ffi::OsString;
-use std::os::windows::ffi::OsStringExt;
-use windows::Win32::Foundation::HANDLE;
-use windows::Win32::System::Com::CoInitializeEx;
-use windows::Win32::System::Com::CoTaskMemFree;
-use windows::Win32::System::Com::CoUninitialize;
-use windows::Win32::System::Com::COINIT_APARTMENTTHREADED;
-use windows::Win32::UI::Shell::SHAddToRecentDocs;
-use windows::Win32::UI::Shell::{FOLDERID_Recent, SHGetKnownFolderPath, KNOWN_FOLDER_FLAG};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use windows::Win32::UI::Shell::{SHAddToRecentDocs, SHARD_PATHW};
 
 /// Clears the Windows Recent Files list using the Windows Shell API.
+///
+/// `SHAddToRecentDocs` with a null path clears the recent documents history, which
+/// empties the recent files shown in Quick Access. Recently used folders are tracked
+/// separately: their shortcuts in the Recent folder and the frequent folders jump list
+/// are not guaranteed to be cleared, see [`empty_recent_folders_with_shortcuts`].
 pub(crate) fn empty_recent_files_with_api() -> WincentResult<()> {
+    let _guard = lock_mutations();
+    let _com = ComGuard::init()?;
+
     unsafe {
-        let hr = CoInitializeEx(Some(std::ptr::null_mut()), COINIT_APARTMENTTHREADED);
-        if hr.is_err() {
-            return Err(WincentError::WindowsApi(hr.0));
+        SHAddToRecentDocs(SHARD_PATHW.0 as u32, None);
+    }
+
+    Ok(())
+}
+
+/// Clears normal folders from Quick Access by removing the Windows jump list file.
+pub(crate) fn empty_normal_folders_with_jumplist_file() -> WincentResult<()> {
+    let _guard = lock_mutations();
+    for jumplist_file in get_quick_access_data_files(QuickAccess::FrequentFolders)? {
+        if jumplist_file.exists() {
+            std::fs::remove_file(&jumplist_file).map_err(WincentError::Io)?;
         }
+    }
 
-        // 0x0000_0003 equals SHARD_PATHW
-        SHAddToRecentDocs(0x0000_0003, None);
+    Ok(())
+}
+
+/// Clears recently used folders by deleting the folder shortcuts in the Recent folder.
+pub(crate) fn empty_recent_folders_with_shortcuts() -> WincentResult<()> {
+    let recent_folder = get_windows_recent_folder()?;
 
-        CoUninitialize();
+    let shortcuts = list_shortcuts(Path::new(&recent_folder))?;
+
+    let _guard = lock_mutations();
+    for shortcut in shortcuts {
+        if Path::new(&shortcut.target).is_dir() {
+            match std::fs::remove_file(&shortcut.lnk_path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(WincentError::Io(e)),
+            }
+        }
     }
 
     Ok(())
 }
 
-/// Clears normal folders from Quick Access by removing the Windows jump list file.
-pub(crate) fn empty_normal_folders_with_jumplist_file() -> WincentResult<()> {
-    let result = unsafe {
-        SHGetKnownFolderPath(
-            &FOLDERID_Recent,
-            KNOWN_FOLDER_FLAG(0x00),
-            HANDLE(std::ptr::null_mut()),
-        )
-    }?;
-
-    let recent_folder = unsafe {
-        let wide_str = OsString::from_wide(result.as_wide());
-        CoTaskMemFree(Some(result.as_ptr() as _));
-        wide_str
-            .into_string()
-            .map_err(|_| WincentError::SystemError("Invalid UTF-16".to_string()))?
-    };
+/// Picks the items last used before `cutoff`, according to their shortcut timestamps.
+///
+/// Items without a timestamp are picked only with `include_unknown`.
+pub(crate) fn select_older_than(
+    items: &[String],
+    timestamps: &[(String, SystemTime)],
+    cutoff: SystemTime,
+    include_unknown: bool,
+) -> Vec<String> {
+    items
+        .iter()
+        .filter(|item| {
+            let last_used = timestamps
+                .iter()
+                .filter(|(target, _)| paths_equal(target, item))
+                .map(|(_, modified)| *modified)
+                .max();
+
+            match last_used {
+                Some(last_used) => last_used < cutoff,
+                None => include_unknown,
+            }
+        })
+        .cloned()
+        .collect()
+}
 
-    let jumplist_file = std::path::Path::new(&recent_folder)
-        .join("AutomaticDestinations")
-        .join("f01b4d95cf55d32a.automaticDestinations-ms");
+/// Removes the recent files listed in Quick Access one by one through their Shell verb.
+pub(crate) fn empty_visible_recent_files_with_script() -> WincentResult<()> {
+    let files = query_recent_with_ps_script(QuickAccess::RecentFiles)?;
 
-    if jumplist_file.exists() {
-        std::fs::remove_file(&jumplist_file).map_err(WincentError::Io)?;
+    for file in files {
+        ok_if_missing(remove_recent_files_with_ps_script(&file))?;
     }
 
     Ok(())
 }
 
+/// Checks whether a path has one of the extensions, ignoring case and a leading dot.
+pub(crate) fn has_extension(path: &str, extensions: &[&str]) -> bool {
+    let Some(extension) = Path::new(path).extension().and_then(|ext| ext.to_str()) else {
+        return false;
+    };
+
+    extensions.iter().any(|wanted| {
+        wanted
+            .trim_start_matches('.')
+            .eq_ignore_ascii_case(extension)
+    })
+}
+
 /// Removes all pinned folders from Quick Access using PowerShell commands.
 pub(crate) fn empty_pinned_folders_with_script() -> WincentResult<()> {
     let folders = query_recent_with_ps_script(QuickAccess::FrequentFolders)?;
 
     for folder in folders {
-        unpin_frequent_folder_with_ps_script(&folder)?;
+        ok_if_missing(unpin_frequent_folder_with_ps_script(&folder))?;
     }
 
     Ok(())
@@ -128,14 +189,151 @@ pub(crate) fn empty_pinned_folders_with_script() -> WincentResult<()> {
 /// ```
 pub fn empty_recent_files() -> WincentResult<()> {
     if !check_script_feasible()? {
-        return Err(WincentError::UnsupportedOperation(
-            "PowerShell script execution is not feasible".to_string(),
-        ));
+        return Err(WincentError::ExecutionPolicyRestricted);
     }
 
     empty_recent_files_with_api()
 }
 
+/// Removes the recent files shown in Quick Access, keeping the recent documents history.
+///
+/// Each entry is removed like "Remove from Quick Access" in Explorer. Unlike
+/// [`empty_recent_files`], which clears the whole recent documents history kept by
+/// Windows, the shortcuts in the Recent folder are left in place, so the files stay
+/// listed in places such as application jump lists and the Recent folder itself.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{empty::empty_visible_recent_files, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     // Tidy up Quick Access without losing the recent documents history
+///     empty_visible_recent_files()?;
+///     Ok(())
+/// }
+/// ```
+pub fn empty_visible_recent_files() -> WincentResult<()> {
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    empty_visible_recent_files_with_script()
+}
+
+/// Clears recent items from Windows, optionally including recently used folders.
+///
+/// Recent files are cleared as with [`empty_recent_files`]. With `include_folders`,
+/// recently used folders are cleared too, see [`empty_recent_folders`]: the folder
+/// shortcuts in the Recent folder are deleted and the frequent folders jump list is
+/// removed. Pinned folders are pinned again afterwards, so they are kept.
+///
+/// # Arguments
+///
+/// * `include_folders` - Whether recently used folders should be cleared as well
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{empty::empty_recent_items, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     // Clear all recent files and folders
+///     empty_recent_items(true)?;
+///     Ok(())
+/// }
+/// ```
+pub fn empty_recent_items(include_folders: bool) -> WincentResult<()> {
+    empty_recent_files()?;
+
+    if include_folders {
+        empty_recent_folders()?;
+    }
+
+    Ok(())
+}
+
+/// Clears recently used folders, keeping recent files and pinned folders.
+///
+/// Windows records recently used folders in two places, both are cleared:
+///
+/// - a `.lnk` shortcut per folder in the Recent folder (`%APPDATA%\Microsoft\Windows\Recent`),
+///   these shortcuts are deleted while file shortcuts are kept;
+/// - the frequent folders jump list (`AutomaticDestinations\f01b4d95cf55d32a.automaticDestinations-ms`),
+///   which is removed and rebuilt by Windows on the next folder access.
+///
+/// The jump list also stores the pins, so the pinned folders are queried first and
+/// pinned again in their order once it is removed. Clearing the recent documents history
+/// with `SHAddToRecentDocs`, as [`empty_recent_files`] does, is not guaranteed to touch
+/// either of them.
+///
+/// # Returns
+///
+/// Returns `Ok(())` if the recent folders were cleared and the pins restored, or
+/// [`WincentError::ScriptFailed`] listing the pinned folders that could not be pinned
+/// again, for example because they were deleted.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{empty::empty_recent_folders, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     empty_recent_folders()?;
+///     println!("Recent folders cleared, recent files are kept");
+///     Ok(())
+/// }
+/// ```
+pub fn empty_recent_folders() -> WincentResult<()> {
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    let pinned = query_pinned_with_ps_script()?;
+    if !pinned.is_empty() {
+        // Make sure the pins can be put back before removing them with the jump list
+        ensure_pinunpin_feasible("Pin")?;
+    }
+
+    empty_recent_folders_with_shortcuts()?;
+    empty_normal_folders_with_jumplist_file()?;
+
+    repin_folders(&pinned)
+}
+
+/// Deletes the frequent folders jump list file if it is corrupted, so Windows regenerates it.
+///
+/// This is a targeted recovery for queries failing because of a damaged jump list, a
+/// healthy or missing file is left untouched. Repairing loses the recorded frequent
+/// folder history and may reset pinned folders to the Windows defaults, consider a
+/// [`crate::backup::create_backup`] first if the pins can still be queried.
+///
+/// # Returns
+///
+/// Returns `true` if a corrupted jump list was deleted.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{empty::repair_jumplist, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     if repair_jumplist()? {
+///         println!("Corrupted jump list removed, Windows will rebuild it");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn repair_jumplist() -> WincentResult<bool> {
+    if validate_jumplist()? != JumplistHealth::Corrupt {
+        return Ok(false);
+    }
+
+    empty_normal_folders_with_jumplist_file()?;
+
+    Ok(true)
+}
+
 /// Clears all items from the Windows Frequent Folders list, including both pinned and normal folders.
 ///
 /// # Returns
@@ -156,9 +354,7 @@ pub fn empty_recent_files() -> WincentResult<()> {
 /// ```
 pub fn empty_frequent_folders() -> WincentResult<()> {
     if !check_script_feasible()? {
-        return Err(WincentError::UnsupportedOperation(
-            "PowerShell script execution is not feasible".to_string(),
-        ));
+        return Err(WincentError::ExecutionPolicyRestricted);
     }
 
     empty_normal_folders_with_jumplist_file()?;
@@ -190,13 +386,299 @@ pub fn empty_quick_access() -> WincentResult<()> {
     Ok(())
 }
 
+/// Deletes all values of a registry key, returning how many were deleted.
+pub(crate) fn clear_registry_values(key: &winreg::RegKey) -> WincentResult<usize> {
+    let names = key
+        .enum_values()
+        .map(|value| value.map(|(name, _)| name))
+        .collect::<std::io::Result<Vec<String>>>()?;
+
+    for name in &names {
+        key.delete_value(name)?;
+    }
+
+    Ok(names.len())
+}
+
+/// Clears the paths typed into the Explorer address bar.
+///
+/// Explorer suggests them apart from Quick Access, from the values of
+/// `HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Explorer\TypedPaths`,
+/// so clearing recent items alone leaves them visible. Explorer windows already open
+/// keep showing their list until closed.
+///
+/// # Returns
+///
+/// Returns the number of typed paths removed.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{empty::empty_address_bar_mru, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let removed = empty_address_bar_mru()?;
+///     println!("Removed {} typed paths", removed);
+///     Ok(())
+/// }
+/// ```
+pub fn empty_address_bar_mru() -> WincentResult<usize> {
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_READ, KEY_WRITE};
+    use winreg::RegKey;
+
+    let key = match RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey_with_flags(TYPED_PATHS_KEY, KEY_READ | KEY_WRITE)
+    {
+        Ok(key) => key,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(WincentError::Io(e)),
+    };
+
+    let _guard = lock_mutations();
+    clear_registry_values(&key)
+}
+
+/// Clears Quick Access and the paths typed into the Explorer address bar.
+///
+/// Runs [`empty_quick_access`] followed by [`empty_address_bar_mru`], for a complete
+/// "clear my recent activity".
+///
+/// # Returns
+///
+/// Returns the number of typed paths removed.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{empty::empty_quick_access_full, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     empty_quick_access_full()?;
+///     println!("Recent activity cleared");
+///     Ok(())
+/// }
+/// ```
+pub fn empty_quick_access_full() -> WincentResult<usize> {
+    empty_quick_access()?;
+    empty_address_bar_mru()
+}
+
+/// Clears a Quick Access category after writing a restorable backup of it.
+///
+/// The backup contains the cleared items and the current visibility settings, and can be
+/// restored with [`crate::backup::restore_backup`]. Nothing is cleared if the backup
+/// cannot be written.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to clear
+/// * `backup_path` - The file the backup is written to
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use wincent::{empty::empty_with_backup, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     empty_with_backup(QuickAccess::All, Path::new("quick_access.bak"))?;
+///     println!("Quick Access cleared, backup saved");
+///     Ok(())
+/// }
+/// ```
+pub fn empty_with_backup(qa_type: QuickAccess, backup_path: &Path) -> WincentResult<()> {
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    let backup = capture_backup(qa_type)?;
+    std::fs::write(backup_path, backup.to_text()).map_err(WincentError::Io)?;
+
+    match qa_type {
+        QuickAccess::RecentFiles => empty_recent_files(),
+        QuickAccess::FrequentFolders => empty_frequent_folders(),
+        QuickAccess::All => empty_quick_access(),
+    }
+}
+
+/// Removes recent files that have not been used for longer than a given age.
+///
+/// The last use of a file is the modification time of its shortcut in the Recent
+/// folder, which Windows updates whenever the file is opened again. Recent files
+/// without such a shortcut have an unknown age and are kept, unless `include_unknown`
+/// is set.
+///
+/// # Arguments
+///
+/// * `age` - Recent files last used longer ago than this are removed
+/// * `include_unknown` - Whether recent files with an unknown age are removed too
+///
+/// # Returns
+///
+/// Returns the number of removed recent files.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wincent::{empty::clear_recent_older_than, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     // Keep only the files used in the last 30 days
+///     let removed = clear_recent_older_than(Duration::from_secs(30 * 24 * 60 * 60), false)?;
+///     println!("Removed {} old recent files", removed);
+///     Ok(())
+/// }
+/// ```
+pub fn clear_recent_older_than(age: Duration, include_unknown: bool) -> WincentResult<usize> {
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    // An age reaching before the epoch leaves nothing old enough
+    let Some(cutoff) = SystemTime::now().checked_sub(age) else {
+        return Ok(0);
+    };
+
+    let recent_files = query_recent_with_ps_script(QuickAccess::RecentFiles)?;
+    let recent_folder = get_windows_recent_folder()?;
+    let timestamps: Vec<(String, SystemTime)> = list_shortcuts(Path::new(&recent_folder))?
+        .into_iter()
+        .map(|shortcut| (shortcut.target, shortcut.modified))
+        .collect();
+
+    let expired = select_older_than(&recent_files, &timestamps, cutoff, include_unknown);
+    for file in &expired {
+        ok_if_missing(remove_recent_files_with_ps_script(file))?;
+    }
+
+    Ok(expired.len())
+}
+
+/// Removes the recent files opened with an application, identified by its file types.
+///
+/// Windows does not record which application opened a recent file, so the files are
+/// picked by extension. For example `&["psd", "psb"]` removes the Photoshop documents
+/// from Recent Files. Other recent files are kept.
+///
+/// # Arguments
+///
+/// * `extensions` - File extensions handled by the application, with or without the leading dot
+///
+/// # Returns
+///
+/// Returns the number of removed recent files.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{empty::clear_recents_for_app, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     // Remove all Photoshop documents from Recent Files
+///     let removed = clear_recents_for_app(&["psd", "psb"])?;
+///     println!("Removed {} Photoshop recents", removed);
+///     Ok(())
+/// }
+/// ```
+pub fn clear_recents_for_app(extensions: &[&str]) -> WincentResult<usize> {
+    if extensions
+        .iter()
+        .all(|ext| ext.trim_start_matches('.').is_empty())
+    {
+        return Err(WincentError::InvalidArgument(
+            "No file extension provided".to_string(),
+        ));
+    }
+
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    let matching: Vec<String> = query_recent_with_ps_script(QuickAccess::RecentFiles)?
+        .into_iter()
+        .filter(|file| has_extension(file, extensions))
+        .collect();
+
+    for file in &matching {
+        ok_if_missing(remove_recent_files_with_ps_script(file))?;
+    }
+
+    Ok(matching.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::handle::{add_file_to_recent_with_api, pin_frequent_folder_with_ps_script};
-    use crate::test_utils::{cleanup_test_env, create_test_file, setup_test_env};
+    use crate::test_utils::{
+        cleanup_test_env, create_test_file, create_test_shortcut, setup_test_env,
+    };
     use std::thread;
-    use std::time::Duration;
+
+    #[test]
+    fn test_clear_registry_values() -> WincentResult<()> {
+        use winreg::enums::HKEY_CURRENT_USER;
+        use winreg::RegKey;
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let key_path = "Software\\wincent\\test_typed_paths";
+        let (key, _) = hkcu.create_subkey(key_path)?;
+        key.set_value("url1", &"C:\\First")?;
+        key.set_value("url2", &"D:\\Second")?;
+
+        assert_eq!(clear_registry_values(&key)?, 2);
+        assert_eq!(key.enum_values().count(), 0);
+        assert_eq!(clear_registry_values(&key)?, 0);
+
+        hkcu.delete_subkey_all(key_path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_extension() {
+        assert!(has_extension("C:\\Art\\cover.PSD", &["psd"]));
+        assert!(has_extension("C:\\Art\\cover.psb", &["psd", ".psb"]));
+        assert!(!has_extension("C:\\Art\\cover.psd.txt", &["psd"]));
+        assert!(!has_extension("C:\\Art\\psd", &["psd"]));
+    }
+
+    #[test]
+    fn test_clear_recents_for_app_requires_extension() {
+        for extensions in [&[][..], &[""][..], &["."][..]] {
+            assert!(matches!(
+                clear_recents_for_app(extensions),
+                Err(WincentError::InvalidArgument(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_select_older_than() {
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        let items = vec![
+            "C:\\old.txt".to_string(),
+            "C:\\new.txt".to_string(),
+            "C:\\unknown.txt".to_string(),
+        ];
+        let timestamps = vec![
+            ("c:\\OLD.txt".to_string(), now - day * 40),
+            ("C:\\new.txt".to_string(), now - day * 40),
+            ("C:\\new.txt".to_string(), now - day),
+        ];
+        let cutoff = now - day * 30;
+
+        assert_eq!(
+            select_older_than(&items, &timestamps, cutoff, false),
+            vec!["C:\\old.txt".to_string()],
+            "Newest shortcut of an item should count"
+        );
+        assert_eq!(
+            select_older_than(&items, &timestamps, cutoff, true),
+            vec!["C:\\old.txt".to_string(), "C:\\unknown.txt".to_string()]
+        );
+    }
 
     fn wait_for_files_empty(max_retries: u32) -> WincentResult<bool> {
         for _ in 0..max_retries {
@@ -245,6 +727,126 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[ignore]
+    fn test_empty_visible_recent_files_keeps_history() -> WincentResult<()> {
+        let test_dir = setup_test_env()?;
+
+        let test_file = create_test_file(&test_dir, "visible.txt", "content")?;
+        let test_path = test_file.to_str().unwrap().to_string();
+        add_file_to_recent_with_api(&test_path)?;
+        thread::sleep(Duration::from_secs(1));
+
+        empty_visible_recent_files_with_script()?;
+        assert!(
+            wait_for_files_empty(5)?,
+            "Recent files list should be empty"
+        );
+
+        let recent_folder = get_windows_recent_folder()?;
+        let history_kept = list_shortcuts(Path::new(&recent_folder))?
+            .iter()
+            .any(|shortcut| paths_equal(&shortcut.target, &test_path));
+        assert!(
+            history_kept,
+            "Shortcut in the Recent folder should be kept, unlike with the API clear"
+        );
+
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn test_empty_recent_items_with_folders() -> WincentResult<()> {
+        let test_dir = setup_test_env()?;
+
+        let test_file = create_test_file(&test_dir, "test.txt", "content")?;
+        add_file_to_recent_with_api(test_file.to_str().unwrap())?;
+        thread::sleep(Duration::from_secs(1));
+
+        empty_recent_items(true)?;
+
+        assert!(
+            wait_for_files_empty(5)?,
+            "Recent files list should be empty"
+        );
+
+        let recent_folder = get_windows_recent_folder()?;
+        let folder_shortcuts: Vec<_> = list_shortcuts(Path::new(&recent_folder))?
+            .into_iter()
+            .filter(|shortcut| Path::new(&shortcut.target).is_dir())
+            .collect();
+        assert!(
+            folder_shortcuts.is_empty(),
+            "Recent folders should be empty"
+        );
+
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn test_empty_recent_items_keeps_pinned_folders() -> WincentResult<()> {
+        let test_dir = setup_test_env()?;
+        let folder = test_dir.join("pinned");
+        std::fs::create_dir_all(&folder)?;
+        let folder = folder.to_str().unwrap();
+
+        pin_frequent_folder_with_ps_script(folder)?;
+        let pinned_before = query_pinned_with_ps_script()?;
+
+        empty_recent_items(true)?;
+
+        let pinned_after = query_pinned_with_ps_script()?;
+        assert!(
+            pinned_after.iter().any(|item| paths_equal(item, folder)),
+            "Pinned folder should still be pinned"
+        );
+        assert_eq!(
+            pinned_after.len(),
+            pinned_before.len(),
+            "Every pinned folder should be kept"
+        );
+
+        ok_if_missing(unpin_frequent_folder_with_ps_script(folder))?;
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn test_empty_recent_folders() -> WincentResult<()> {
+        let test_dir = setup_test_env()?;
+        let recent_folder = get_windows_recent_folder()?;
+        let folder_shortcut =
+            create_test_shortcut(Path::new(&recent_folder), "wincent_folder.lnk", &test_dir)?;
+        let test_file = create_test_file(&test_dir, "kept.txt", "content")?;
+        let file_shortcut = create_test_shortcut(
+            Path::new(&recent_folder),
+            "wincent_kept.txt.lnk",
+            &test_file,
+        )?;
+
+        empty_recent_folders()?;
+
+        let folder_shortcuts: Vec<_> = list_shortcuts(Path::new(&recent_folder))?
+            .into_iter()
+            .filter(|shortcut| Path::new(&shortcut.target).is_dir())
+            .collect();
+        assert!(
+            folder_shortcuts.is_empty(),
+            "Recent folders should be empty"
+        );
+        assert!(!folder_shortcut.exists());
+        assert!(file_shortcut.exists(), "File shortcuts should be kept");
+
+        std::fs::remove_file(&file_shortcut)?;
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
     #[test]
     #[ignore]
     fn test_empty_normal_folders() -> WincentResult<()> {
@@ -11,6 +11,12 @@ pub enum WincentError {
     #[error("PowerShell execution failed: {0}")]
     PowerShellExecution(String),
 
+    #[error("PowerShell executable not found")]
+    PowerShellNotFound,
+
+    #[error("Failed to spawn PowerShell: {0}")]
+    PowerShellSpawn(#[source] std::io::Error),
+
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
@@ -32,13 +38,39 @@ pub enum WincentError {
     #[error("Unknown script method: {0}")]
     UnknownScriptMethod(u32),
 
+    #[error("Missing function parameter")]
+    MissingParameter,
+
+    #[deprecated(
+        since = "0.1.2",
+        note = "misspelled, use `WincentError::MissingParameter` instead"
+    )]
     #[error("Missing function parameter")]
     MissingParemeter,
 
+    #[error("Invalid backup: {0}")]
+    InvalidBackup(String),
+
     #[error("Windows API error: {0}")]
     WindowsApi(i32),
+
+    #[error("Operation timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Item already exists in Quick Access: {0}")]
+    AlreadyExists(String),
+
+    #[error("Item not found in Quick Access: {0}")]
+    NotInQuickAccess(String),
+
+    #[error("PowerShell script execution is not feasible: blocked by the execution policy")]
+    ExecutionPolicyRestricted,
 }
 
+#[cfg(windows)]
 impl From<windows::core::Error> for WincentError {
     fn from(err: windows::core::Error) -> Self {
         WincentError::WindowsApi(err.code().0)
@@ -52,6 +84,7 @@ mod tests {
     use std::io::{Error, ErrorKind};
 
     #[test]
+    #[allow(deprecated)]
     fn test_error_conversions() {
         let io_error = Error::new(ErrorKind::NotFound, "file not found");
         let wincent_error = WincentError::from(io_error);
@@ -60,11 +93,48 @@ mod tests {
         let missing_param = WincentError::MissingParemeter;
         assert!(format!("{}", missing_param).contains("Missing function parameter"));
 
+        let missing_param = WincentError::MissingParameter;
+        assert!(format!("{}", missing_param).contains("Missing function parameter"));
+
         let invalid_path = WincentError::InvalidPath("test/path".to_string());
         assert!(format!("{}", invalid_path).contains("test/path"));
 
         let ps_error = WincentError::PowerShellExecution("access denied".to_string());
         assert!(format!("{}", ps_error).contains("access denied"));
+
+        let ps_missing = WincentError::PowerShellNotFound;
+        assert!(format!("{}", ps_missing).contains("not found"));
+
+        let timeout = WincentError::Timeout(std::time::Duration::from_secs(3));
+        assert!(format!("{}", timeout).contains("timed out after 3s"));
+    }
+
+    #[test]
+    fn test_error_source_chain() {
+        use std::error::Error as _;
+
+        let spawn_error =
+            WincentError::PowerShellSpawn(Error::new(ErrorKind::PermissionDenied, "access denied"));
+        let source = spawn_error
+            .source()
+            .expect("Spawn error should have a source");
+        let io_error = source
+            .downcast_ref::<Error>()
+            .expect("Source should be the io error");
+        assert_eq!(io_error.kind(), ErrorKind::PermissionDenied);
+        assert!(
+            source.source().is_none(),
+            "Chain should end at the io error"
+        );
+
+        let io_wrapped = WincentError::from(Error::new(ErrorKind::NotFound, "missing"));
+        assert!(
+            io_wrapped.source().is_some(),
+            "Io error should expose its source"
+        );
+
+        let message_only = WincentError::ScriptFailed("failed".to_string());
+        assert!(message_only.source().is_none());
     }
 
     #[test]
@@ -72,7 +142,7 @@ mod tests {
         let success: WincentResult<()> = Ok(());
         assert!(success.is_ok());
 
-        let failure: WincentResult<()> = Err(WincentError::MissingParemeter);
+        let failure: WincentResult<()> = Err(WincentError::MissingParameter);
         assert!(failure.is_err());
     }
 }
@@ -22,10 +22,88 @@
 
 use crate::{
     error::WincentError,
-    scripts::{execute_ps_script, Script},
+    scripts::{
+        execute_ps_content, execute_ps_script, execute_ps_script_with_timeout, get_script_content,
+        Script, EXIT_NAMESPACE_UNAVAILABLE, EXIT_TIMED_OUT,
+    },
     utils, WincentResult,
 };
 use std::path::Path;
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// PowerShell execution policy of the current scope, as stored in the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionPolicy {
+    /// No scripts can run.
+    Restricted,
+    /// Only scripts signed by a trusted publisher can run.
+    AllSigned,
+    /// Downloaded scripts must be signed, local scripts can run.
+    RemoteSigned,
+    /// All scripts can run, downloaded ones after a warning.
+    Unrestricted,
+    /// All scripts run without warnings.
+    Bypass,
+    /// No policy is set in this scope, or the stored value is not recognized.
+    Undefined,
+}
+
+impl ExecutionPolicy {
+    /// Returns the policy name as used by PowerShell.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionPolicy::Restricted => "Restricted",
+            ExecutionPolicy::AllSigned => "AllSigned",
+            ExecutionPolicy::RemoteSigned => "RemoteSigned",
+            ExecutionPolicy::Unrestricted => "Unrestricted",
+            ExecutionPolicy::Bypass => "Bypass",
+            ExecutionPolicy::Undefined => "Undefined",
+        }
+    }
+
+    /// Returns `true` if the policy lets wincent run its generated scripts.
+    pub fn allows_scripts(&self) -> bool {
+        matches!(
+            self,
+            ExecutionPolicy::AllSigned
+                | ExecutionPolicy::RemoteSigned
+                | ExecutionPolicy::Unrestricted
+                | ExecutionPolicy::Bypass
+        )
+    }
+
+    /// Parses a policy name, ignoring case.
+    pub(crate) fn from_name(name: &str) -> ExecutionPolicy {
+        [
+            ExecutionPolicy::Restricted,
+            ExecutionPolicy::AllSigned,
+            ExecutionPolicy::RemoteSigned,
+            ExecutionPolicy::Unrestricted,
+            ExecutionPolicy::Bypass,
+        ]
+        .into_iter()
+        .find(|policy| policy.as_str().eq_ignore_ascii_case(name.trim()))
+        .unwrap_or(ExecutionPolicy::Undefined)
+    }
+}
+
+impl std::fmt::Display for ExecutionPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Changes made by [`fix_script_feasible`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixReport {
+    /// `true` if the execution policy was changed.
+    pub changed: bool,
+    /// The execution policy before the fix.
+    pub previous: ExecutionPolicy,
+    /// The execution policy after the fix.
+    pub current: ExecutionPolicy,
+}
 
 /// Retrieves the registry key for the PowerShell execution policy.
 fn get_execution_policy_reg() -> WincentResult<winreg::RegKey> {
@@ -49,40 +127,49 @@ fn get_execution_policy_reg() -> WincentResult<winreg::RegKey> {
     }
 }
 
-/// Checks if the PowerShell execution policy is feasible based on the registry value.
-pub(crate) fn check_script_feasible_with_registry() -> WincentResult<bool> {
-    let reg_value = "ExecutionPolicy";
-    let reg_key = get_execution_policy_reg()?;
-    let feasible_options = ["AllSigned", "Bypass", "RemoteSigned", "Unrestricted"];
-
-    match reg_key.get_raw_value(reg_value) {
+/// Reads the PowerShell execution policy from its registry key.
+fn read_execution_policy(reg_key: &winreg::RegKey) -> WincentResult<ExecutionPolicy> {
+    match reg_key.get_raw_value("ExecutionPolicy") {
         Ok(val) => {
             // raw reg value vec will contains '\n' between characters, needs to filter
             let filtered_vec: Vec<u8> = val.bytes.into_iter().filter(|&x| x != 0).collect();
             let val_in_string = String::from_utf8_lossy(&filtered_vec).to_string();
-            let _res = feasible_options.contains(&val_in_string.as_str());
-            Ok(feasible_options.contains(&val_in_string.as_str()))
-        }
-        Err(e) => {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                Ok(false)
-            } else {
-                Err(WincentError::Io(e))
-            }
+            Ok(ExecutionPolicy::from_name(&val_in_string))
         }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ExecutionPolicy::Undefined),
+        Err(e) => Err(WincentError::Io(e)),
     }
 }
 
+/// Gets the PowerShell execution policy of the scope wincent checks and fixes.
+pub(crate) fn current_execution_policy() -> WincentResult<ExecutionPolicy> {
+    read_execution_policy(&get_execution_policy_reg()?)
+}
+
+/// Checks if the PowerShell execution policy is feasible based on the registry value.
+pub(crate) fn check_script_feasible_with_registry() -> WincentResult<bool> {
+    let reg_key = get_execution_policy_reg()?;
+
+    Ok(read_execution_policy(&reg_key)?.allows_scripts())
+}
+
 /// Sets the PowerShell execution policy to "RemoteSigned" in the registry.
-pub(crate) fn fix_script_feasible_with_registry() -> WincentResult<()> {
+pub(crate) fn fix_script_feasible_with_registry() -> WincentResult<FixReport> {
     let reg_value = "ExecutionPolicy";
     let reg_key = get_execution_policy_reg()?;
+    let previous = read_execution_policy(&reg_key)?;
 
     reg_key
-        .set_value(reg_value, &"RemoteSigned")
+        .set_value(reg_value, &ExecutionPolicy::RemoteSigned.as_str())
         .map_err(WincentError::Io)?;
 
-    Ok(())
+    let current = read_execution_policy(&reg_key)?;
+
+    Ok(FixReport {
+        changed: previous != current,
+        previous,
+        current,
+    })
 }
 
 /// Checks if PowerShell query commands are available and executable.
@@ -92,6 +179,69 @@ pub(crate) fn check_query_feasible_with_script() -> WincentResult<bool> {
     Ok(output.status.success())
 }
 
+/// Outcome of [`check_query_feasible_detailed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeasibilityOutcome {
+    /// Quick Access can be queried.
+    Ok,
+    /// PowerShell is not installed or not on `PATH`.
+    PowerShellMissing,
+    /// The execution policy, usually enforced by group policy, blocks the script.
+    PolicyRestricted,
+    /// The Quick Access shell namespace could not be opened.
+    NamespaceUnavailable,
+    /// The query did not finish in time.
+    TimedOut,
+}
+
+/// Fragments PowerShell prints when the execution policy blocks a script.
+const POLICY_RESTRICTED_MARKERS: [&str; 3] = [
+    "running scripts is disabled",
+    "UnauthorizedAccess",
+    "PSSecurityException",
+];
+
+/// Derives the outcome of the query check from its exit code and error output.
+///
+/// Failures without a recognized cause are returned as [`WincentError::ScriptFailed`].
+pub(crate) fn classify_query_check(
+    code: Option<i32>,
+    stderr: &str,
+) -> WincentResult<FeasibilityOutcome> {
+    match code {
+        Some(0) => Ok(FeasibilityOutcome::Ok),
+        Some(EXIT_TIMED_OUT) => Ok(FeasibilityOutcome::TimedOut),
+        Some(EXIT_NAMESPACE_UNAVAILABLE) => Ok(FeasibilityOutcome::NamespaceUnavailable),
+        _ if POLICY_RESTRICTED_MARKERS
+            .iter()
+            .any(|marker| stderr.contains(marker)) =>
+        {
+            Ok(FeasibilityOutcome::PolicyRestricted)
+        }
+        _ => Err(WincentError::ScriptFailed(stderr.to_string())),
+    }
+}
+
+/// Checks if PowerShell query commands are available, reporting why they are not.
+///
+/// The check always runs from a temporary `.ps1` file like the mutation scripts, since
+/// the execution policy does not apply to inline commands and could not be detected.
+pub(crate) fn check_query_feasible_detailed_with_script() -> WincentResult<FeasibilityOutcome> {
+    let content = get_script_content(Script::CheckQueryFeasible, None)?;
+    let name = Script::CheckQueryFeasible.name();
+    let output = match execute_ps_content(name, &content, false, None) {
+        Ok(output) => output,
+        Err(WincentError::PowerShellNotFound) => return Ok(FeasibilityOutcome::PowerShellMissing),
+        Err(WincentError::Timeout(_)) => return Ok(FeasibilityOutcome::TimedOut),
+        Err(e) => return Err(e),
+    };
+
+    classify_query_check(
+        output.status.code(),
+        &String::from_utf8_lossy(&output.stderr),
+    )
+}
+
 /// Checks if PowerShell pin/unpin commands are available and executable.
 pub(crate) fn check_pinunpin_feasible_with_script() -> WincentResult<bool> {
     let output = execute_ps_script(Script::CheckPinUnpinFeasible, None)?;
@@ -99,6 +249,44 @@ pub(crate) fn check_pinunpin_feasible_with_script() -> WincentResult<bool> {
     Ok(output.status.success())
 }
 
+/// Runs two checks concurrently, sharing one deadline.
+///
+/// Each check gets the whole budget as its own timeout. A check that times out or does
+/// not report back before the deadline yields `None`, other errors are returned.
+pub(crate) fn run_checks_within<Q, P>(
+    budget: Duration,
+    query: Q,
+    pinunpin: P,
+) -> WincentResult<(Option<bool>, Option<bool>)>
+where
+    Q: FnOnce(Duration) -> WincentResult<bool> + Send + 'static,
+    P: FnOnce(Duration) -> WincentResult<bool> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+
+    let query_sender = sender.clone();
+    std::thread::spawn(move || {
+        let _ = query_sender.send((0, query(budget)));
+    });
+    std::thread::spawn(move || {
+        let _ = sender.send((1, pinunpin(budget)));
+    });
+
+    let mut results = [None, None];
+    let deadline = Instant::now() + budget;
+    while let Ok((index, result)) =
+        receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+    {
+        results[index] = match result {
+            Ok(feasible) => Some(feasible),
+            Err(WincentError::Timeout(_)) => None,
+            Err(e) => return Err(e),
+        };
+    }
+
+    Ok((results[0], results[1]))
+}
+
 /// Checks if a registry path exists.
 #[allow(dead_code)]
 fn registry_path_exists(path: &Path) -> bool {
@@ -139,6 +327,85 @@ fn get_execution_policy() -> WincentResult<String> {
     }
 }
 
+const POLICIES_EXPLORER_KEY: &str =
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer";
+const EXPLORER_ADVANCED_KEY: &str =
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Advanced";
+
+/// Reads a DWORD registry value, `None` if the key or value does not exist.
+fn read_dword(hkey: winreg::HKEY, key_path: &str, value_name: &str) -> WincentResult<Option<u32>> {
+    use winreg::enums::KEY_READ;
+    use winreg::RegKey;
+
+    let reg_key = match RegKey::predef(hkey).open_subkey_with_flags(key_path, KEY_READ) {
+        Ok(key) => key,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(WincentError::Io(e)),
+    };
+
+    match reg_key.get_value::<u32, _>(value_name) {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(WincentError::Io(e)),
+    }
+}
+
+/// Decides whether Windows records recent documents from the relevant registry values.
+///
+/// Any `NoRecentDocsHistory` policy other than `0` turns tracking off, as does the
+/// "Show recently opened items" setting (`Start_TrackDocs`) set to `0`.
+pub(crate) fn recent_tracking_allowed(
+    machine_policy: Option<u32>,
+    user_policy: Option<u32>,
+    track_docs: Option<u32>,
+) -> bool {
+    let disabled_by_policy = machine_policy.or(user_policy).is_some_and(|v| v != 0);
+    let disabled_by_user = track_docs == Some(0);
+
+    !disabled_by_policy && !disabled_by_user
+}
+
+/// Checks the registry for settings preventing recent documents from being recorded.
+pub(crate) fn check_recent_tracking_with_registry() -> WincentResult<bool> {
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+
+    let machine_policy = read_dword(
+        HKEY_LOCAL_MACHINE,
+        POLICIES_EXPLORER_KEY,
+        "NoRecentDocsHistory",
+    )?;
+    let user_policy = read_dword(
+        HKEY_CURRENT_USER,
+        POLICIES_EXPLORER_KEY,
+        "NoRecentDocsHistory",
+    )?;
+    let track_docs = read_dword(HKEY_CURRENT_USER, EXPLORER_ADVANCED_KEY, "Start_TrackDocs")?;
+
+    Ok(recent_tracking_allowed(
+        machine_policy,
+        user_policy,
+        track_docs,
+    ))
+}
+
+/// Results of [`check_feasible_within`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeasibilityReport {
+    /// `true` if the execution policy allows running scripts.
+    pub script: bool,
+    /// Query check result, `None` if it did not finish within the budget.
+    pub query: Option<bool>,
+    /// Pin/unpin check result, `None` if it did not finish within the budget.
+    pub pinunpin: Option<bool>,
+}
+
+impl FeasibilityReport {
+    /// Returns `true` only if every check finished and succeeded.
+    pub fn is_feasible(&self) -> bool {
+        self.script && self.query == Some(true) && self.pinunpin == Some(true)
+    }
+}
+
 /****************************************************** Feature Feasible ******************************************************/
 
 /// Checks if PowerShell script execution is feasible on the current system.
@@ -167,6 +434,13 @@ pub fn check_script_feasible() -> WincentResult<bool> {
 
 /// Fixes PowerShell script execution policy to allow script execution.
 ///
+/// The execution policy of the current user, or of the machine when running as
+/// administrator, is set to `RemoteSigned`.
+///
+/// # Returns
+///
+/// Returns the execution policy before and after the fix.
+///
 /// # Example
 ///
 /// ```rust
@@ -177,13 +451,19 @@ pub fn check_script_feasible() -> WincentResult<bool> {
 ///
 /// fn main() -> Result<(), WincentError> {
 ///     if !check_script_feasible()? {
-///         fix_script_feasible()?;
+///         let report = fix_script_feasible()?;
+///         if report.changed {
+///             println!(
+///                 "Changed execution policy from {} to {}",
+///                 report.previous, report.current
+///             );
+///         }
 ///         assert!(check_script_feasible()?);
 ///     }
 ///     Ok(())
 /// }
 /// ```
-pub fn fix_script_feasible() -> WincentResult<()> {
+pub fn fix_script_feasible() -> WincentResult<FixReport> {
     fix_script_feasible_with_registry()
 }
 
@@ -211,6 +491,39 @@ pub fn check_query_feasible() -> WincentResult<bool> {
     check_query_feasible_with_script()
 }
 
+/// Checks if Quick Access query operations are feasible, reporting why they are not.
+///
+/// Unlike [`check_query_feasible`], the cause of a failure is kept, so callers can point
+/// the user to the right fix.
+///
+/// # Returns
+///
+/// Returns [`FeasibilityOutcome::Ok`] if queries are supported, otherwise the detected
+/// cause. Failures with no recognized cause are returned as errors.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{
+///     feasible::{check_query_feasible_detailed, FeasibilityOutcome},
+///     error::WincentError,
+/// };
+///
+/// fn main() -> Result<(), WincentError> {
+///     match check_query_feasible_detailed()? {
+///         FeasibilityOutcome::Ok => println!("Quick Access can be queried"),
+///         FeasibilityOutcome::PowerShellMissing => println!("Please install PowerShell"),
+///         FeasibilityOutcome::PolicyRestricted => println!("Scripts are blocked by policy"),
+///         FeasibilityOutcome::NamespaceUnavailable => println!("Quick Access is unavailable"),
+///         FeasibilityOutcome::TimedOut => println!("Explorer did not respond in time"),
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn check_query_feasible_detailed() -> WincentResult<FeasibilityOutcome> {
+    check_query_feasible_detailed_with_script()
+}
+
 /// Checks if pin/unpin operations are feasible on the current system.
 ///
 /// # Returns
@@ -235,6 +548,32 @@ pub fn check_pinunpin_feasible() -> WincentResult<bool> {
     check_pinunpin_feasible_with_script()
 }
 
+/// Checks if Windows records recently opened files.
+///
+/// When the `NoRecentDocsHistory` policy is set, or "Show recently opened items" is
+/// turned off, Windows silently ignores files added to Recent Files, so
+/// [`crate::handle::add_to_recent_files`] succeeds without any effect.
+///
+/// # Returns
+///
+/// Returns `true` if recent files are recorded, `false` if tracking is disabled.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{feasible::is_recent_tracking_enabled, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     if !is_recent_tracking_enabled()? {
+///         println!("Recent files are disabled by policy or user settings");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn is_recent_tracking_enabled() -> WincentResult<bool> {
+    check_recent_tracking_with_registry()
+}
+
 /// Checks if all Quick Access operations are feasible on the current system.
 ///
 /// # Returns
@@ -270,6 +609,66 @@ pub fn check_feasible() -> WincentResult<bool> {
     Ok(query_ok && pinunpin_ok)
 }
 
+/// Checks if all Quick Access operations are feasible, giving up after a total budget.
+///
+/// Unlike [`check_feasible`], the query and pin/unpin checks run concurrently and share
+/// one deadline, so a broken environment costs at most `budget` instead of one script
+/// timeout per check. Checks still running when the budget expires are reported as
+/// unknown. If scripts cannot run at all, the other checks are skipped and reported as
+/// not feasible.
+///
+/// # Arguments
+///
+/// * `budget` - The total time allowed for all checks
+///
+/// # Returns
+///
+/// Returns the result of each check.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wincent::{feasible::check_feasible_within, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let report = check_feasible_within(Duration::from_secs(5))?;
+///     if report.query.is_none() || report.pinunpin.is_none() {
+///         println!("Feasibility checks did not finish in time");
+///     } else if !report.is_feasible() {
+///         println!("Some Quick Access operations are not supported");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn check_feasible_within(budget: Duration) -> WincentResult<FeasibilityReport> {
+    if !check_script_feasible()? {
+        return Ok(FeasibilityReport {
+            script: false,
+            query: Some(false),
+            pinunpin: Some(false),
+        });
+    }
+
+    let (query, pinunpin) = run_checks_within(
+        budget,
+        |timeout| {
+            execute_ps_script_with_timeout(Script::CheckQueryFeasible, None, timeout)
+                .map(|output| output.status.success())
+        },
+        |timeout| {
+            execute_ps_script_with_timeout(Script::CheckPinUnpinFeasible, None, timeout)
+                .map(|output| output.status.success())
+        },
+    )?;
+
+    Ok(FeasibilityReport {
+        script: true,
+        query,
+        pinunpin,
+    })
+}
+
 /// Attempts to fix Quick Access operation feasibility issues.
 ///
 /// # Returns
@@ -294,6 +693,62 @@ pub fn fix_feasible() -> WincentResult<bool> {
     check_feasible()
 }
 
+/// Runs an operation, retrying it once after `fix` if the execution policy blocked it.
+///
+/// `fix` returns whether scripts are allowed afterwards. Other errors are returned as is.
+pub(crate) fn retry_after_fix<T, O, F>(mut operation: O, fix: F) -> WincentResult<T>
+where
+    O: FnMut() -> WincentResult<T>,
+    F: FnOnce() -> WincentResult<bool>,
+{
+    match operation() {
+        Err(WincentError::ExecutionPolicyRestricted) => {
+            if fix()? {
+                operation()
+            } else {
+                Err(WincentError::ExecutionPolicyRestricted)
+            }
+        }
+        result => result,
+    }
+}
+
+/// Runs an operation, fixing the execution policy and retrying once if it blocked it.
+///
+/// Automates the check, fix and retry flow: when the operation fails with
+/// [`WincentError::ExecutionPolicyRestricted`], [`fix_script_feasible`] is applied, and if
+/// scripts are allowed afterwards the operation runs a second time. Any other failure is
+/// returned without fixing anything.
+///
+/// # Arguments
+///
+/// * `operation` - The Quick Access operation to run
+///
+/// # Returns
+///
+/// Returns the result of the last run of the operation.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{feasible::with_auto_fix, query::get_recent_files, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let files = with_auto_fix(get_recent_files)?;
+///     println!("Found {} recent files", files.len());
+///     Ok(())
+/// }
+/// ```
+pub fn with_auto_fix<T, F>(operation: F) -> WincentResult<T>
+where
+    F: FnMut() -> WincentResult<T>,
+{
+    retry_after_fix(operation, || {
+        fix_script_feasible()?;
+        check_script_feasible()
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -314,11 +769,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_run_checks_within_budget() -> WincentResult<()> {
+        let started = Instant::now();
+        let (query, pinunpin) = run_checks_within(
+            Duration::from_millis(200),
+            |_| Ok(true),
+            |_| {
+                std::thread::sleep(Duration::from_secs(5));
+                Ok(true)
+            },
+        )?;
+
+        assert_eq!(query, Some(true));
+        assert_eq!(pinunpin, None, "Slow check should be reported as unknown");
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let (query, pinunpin) = run_checks_within(
+            Duration::from_secs(1),
+            |timeout| Err(WincentError::Timeout(timeout)),
+            |_| Ok(false),
+        )?;
+        assert_eq!(query, None);
+        assert_eq!(pinunpin, Some(false));
+
+        Ok(())
+    }
+
     #[test]
     fn test_fix_script_feasible() -> WincentResult<()> {
         let initial_policy = get_execution_policy()?;
 
-        fix_script_feasible_with_registry()?;
+        let report = fix_script_feasible_with_registry()?;
+        assert_eq!(report.current, ExecutionPolicy::RemoteSigned);
+        assert_eq!(
+            report.changed,
+            initial_policy != "RemoteSigned",
+            "Report should tell whether the policy changed"
+        );
 
         let final_policy = get_execution_policy()?;
         assert_eq!(
@@ -337,6 +825,45 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_execution_policy_from_name() {
+        for policy in [
+            ExecutionPolicy::Restricted,
+            ExecutionPolicy::AllSigned,
+            ExecutionPolicy::RemoteSigned,
+            ExecutionPolicy::Unrestricted,
+            ExecutionPolicy::Bypass,
+        ] {
+            assert_eq!(ExecutionPolicy::from_name(policy.as_str()), policy);
+        }
+        assert_eq!(
+            ExecutionPolicy::from_name("remotesigned"),
+            ExecutionPolicy::RemoteSigned
+        );
+        assert_eq!(ExecutionPolicy::from_name(""), ExecutionPolicy::Undefined);
+        assert_eq!(
+            ExecutionPolicy::from_name("Something"),
+            ExecutionPolicy::Undefined
+        );
+
+        assert!(!ExecutionPolicy::Restricted.allows_scripts());
+        assert!(!ExecutionPolicy::Undefined.allows_scripts());
+        assert!(ExecutionPolicy::RemoteSigned.allows_scripts());
+        assert_eq!(ExecutionPolicy::Bypass.to_string(), "Bypass");
+    }
+
+    #[test]
+    fn test_recent_tracking_allowed() {
+        assert!(recent_tracking_allowed(None, None, None));
+        assert!(recent_tracking_allowed(Some(0), None, Some(1)));
+        assert!(!recent_tracking_allowed(None, Some(1), None));
+        assert!(
+            !recent_tracking_allowed(Some(1), Some(0), None),
+            "Machine policy should take precedence"
+        );
+        assert!(!recent_tracking_allowed(None, None, Some(0)));
+    }
+
     #[test]
     fn test_registry_path_exists() {
         let valid_path = Path::new("HKEY_LOCAL_MACHINE\\SOFTWARE\\Microsoft\\PowerShell\\1\\ShellIds\\Microsoft.PowerShell");
@@ -399,6 +926,85 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_retry_after_fix() {
+        // Retried once the fix succeeds
+        let mut runs = 0;
+        let result = retry_after_fix(
+            || {
+                runs += 1;
+                if runs == 1 {
+                    Err(WincentError::ExecutionPolicyRestricted)
+                } else {
+                    Ok(runs)
+                }
+            },
+            || Ok(true),
+        );
+        assert_eq!(result.unwrap(), 2);
+
+        // Not retried when the fix does not help
+        let mut runs = 0;
+        let result: WincentResult<()> = retry_after_fix(
+            || {
+                runs += 1;
+                Err(WincentError::ExecutionPolicyRestricted)
+            },
+            || Ok(false),
+        );
+        assert!(matches!(
+            result,
+            Err(WincentError::ExecutionPolicyRestricted)
+        ));
+        assert_eq!(runs, 1);
+
+        // Other failures never trigger the fix
+        let result: WincentResult<()> = retry_after_fix(
+            || Err(WincentError::ScriptFailed("failed".to_string())),
+            || panic!("Fix should not run"),
+        );
+        assert!(matches!(result, Err(WincentError::ScriptFailed(_))));
+    }
+
+    #[test]
+    fn test_classify_query_check() {
+        assert_eq!(
+            classify_query_check(Some(0), "").unwrap(),
+            FeasibilityOutcome::Ok
+        );
+        assert_eq!(
+            classify_query_check(Some(EXIT_TIMED_OUT), "timed out").unwrap(),
+            FeasibilityOutcome::TimedOut
+        );
+        assert_eq!(
+            classify_query_check(Some(EXIT_NAMESPACE_UNAVAILABLE), "").unwrap(),
+            FeasibilityOutcome::NamespaceUnavailable
+        );
+        assert_eq!(
+            classify_query_check(
+                Some(1),
+                "File check.ps1 cannot be loaded because running scripts is disabled on this system."
+            )
+            .unwrap(),
+            FeasibilityOutcome::PolicyRestricted
+        );
+        assert!(matches!(
+            classify_query_check(Some(1), "something else"),
+            Err(WincentError::ScriptFailed(_))
+        ));
+    }
+
+    #[test_log::test]
+    #[ignore]
+    fn test_check_query_feasible_detailed() -> WincentResult<()> {
+        let outcome = check_query_feasible_detailed_with_script()?;
+        assert_eq!(
+            outcome == FeasibilityOutcome::Ok,
+            check_query_feasible_with_script()?
+        );
+        Ok(())
+    }
+
     #[test_log::test]
     #[ignore]
     fn test_check_pinunpin_feasible_with_script() -> WincentResult<()> {
@@ -0,0 +1,374 @@
+//! C-compatible bindings, enabled by the `ffi` feature.
+//!
+//! All functions return `WINCENT_OK` (`0`) on success or a negative error code, see the
+//! `WINCENT_ERR_*` constants. Path arguments are null-terminated UTF-8 strings owned by
+//! the caller.
+//!
+//! ## Ownership
+//!
+//! Query functions allocate an array of `count` null-terminated UTF-8 strings and store
+//! it in `*out`. The array and its strings belong to the library and must be released
+//! exactly once with `wincent_free(*out, *count)`, never with the C runtime `free`. An
+//! empty result stores a null array and a count of `0`, which `wincent_free` accepts too.
+//!
+//! ## Building
+//!
+//! The crate is built as an `rlib` by default. To get a library linkable from C or C++:
+//!
+//! ```text
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! ## Example
+//!
+//! ```c
+//! char **files = NULL;
+//! size_t count = 0;
+//!
+//! if (wincent_get_recent_files(&files, &count) == 0) {
+//!     for (size_t i = 0; i < count; i++) {
+//!         printf("%s\n", files[i]);
+//!     }
+//!     wincent_free(files, count);
+//! }
+//! ```
+
+use crate::{
+    error::WincentError,
+    handle::{
+        add_to_frequent_folders, add_to_recent_files, remove_from_frequent_folders,
+        remove_from_recent_files,
+    },
+    query::{get_frequent_folders, get_quick_access_items, get_recent_files},
+    WincentResult,
+};
+use std::ffi::{c_char, CStr, CString};
+
+/// The operation succeeded.
+pub const WINCENT_OK: i32 = 0;
+/// A pointer argument was null, or a string was not valid UTF-8 or contained a nul byte.
+///
+/// Detected by the FFI layer itself while converting between C and Rust; a value the
+/// library rejects is reported as [`WINCENT_ERR_INVALID_ARGUMENT_VALUE`] instead.
+pub const WINCENT_ERR_INVALID_ARGUMENT: i32 = -1;
+/// [`WincentError::Io`]
+pub const WINCENT_ERR_IO: i32 = -2;
+/// [`WincentError::Utf8`]
+pub const WINCENT_ERR_UTF8: i32 = -3;
+/// [`WincentError::PowerShellExecution`]
+pub const WINCENT_ERR_POWERSHELL_EXECUTION: i32 = -4;
+/// [`WincentError::PowerShellNotFound`]
+pub const WINCENT_ERR_POWERSHELL_NOT_FOUND: i32 = -5;
+/// [`WincentError::PowerShellSpawn`]
+pub const WINCENT_ERR_POWERSHELL_SPAWN: i32 = -6;
+/// [`WincentError::InvalidPath`]
+pub const WINCENT_ERR_INVALID_PATH: i32 = -7;
+/// [`WincentError::UnsupportedOperation`]
+pub const WINCENT_ERR_UNSUPPORTED_OPERATION: i32 = -8;
+/// [`WincentError::SystemError`]
+pub const WINCENT_ERR_SYSTEM: i32 = -9;
+/// [`WincentError::ArrayConversion`]
+pub const WINCENT_ERR_ARRAY_CONVERSION: i32 = -10;
+/// [`WincentError::ScriptFailed`]
+pub const WINCENT_ERR_SCRIPT_FAILED: i32 = -11;
+/// [`WincentError::UnknownQuickAccessType`]
+pub const WINCENT_ERR_UNKNOWN_QUICK_ACCESS_TYPE: i32 = -12;
+/// [`WincentError::UnknownScriptMethod`]
+pub const WINCENT_ERR_UNKNOWN_SCRIPT_METHOD: i32 = -13;
+/// [`WincentError::MissingParameter`]
+pub const WINCENT_ERR_MISSING_PARAMETER: i32 = -14;
+/// [`WincentError::InvalidBackup`]
+pub const WINCENT_ERR_INVALID_BACKUP: i32 = -15;
+/// [`WincentError::WindowsApi`]
+pub const WINCENT_ERR_WINDOWS_API: i32 = -16;
+/// [`WincentError::Timeout`]
+pub const WINCENT_ERR_TIMEOUT: i32 = -17;
+/// [`WincentError::InvalidArgument`]: the arguments reached the library but one of
+/// their values was rejected, unlike [`WINCENT_ERR_INVALID_ARGUMENT`] which is never
+/// passed on to the library.
+pub const WINCENT_ERR_INVALID_ARGUMENT_VALUE: i32 = -18;
+/// [`WincentError::AlreadyExists`]
+pub const WINCENT_ERR_ALREADY_EXISTS: i32 = -19;
+/// [`WincentError::NotInQuickAccess`]
+pub const WINCENT_ERR_NOT_IN_QUICK_ACCESS: i32 = -20;
+/// [`WincentError::ExecutionPolicyRestricted`]
+pub const WINCENT_ERR_EXECUTION_POLICY_RESTRICTED: i32 = -21;
+
+/// Maps an error to its stable FFI error code.
+#[allow(deprecated)]
+pub(crate) fn error_code(err: &WincentError) -> i32 {
+    match err {
+        WincentError::Io(_) => WINCENT_ERR_IO,
+        WincentError::Utf8(_) => WINCENT_ERR_UTF8,
+        WincentError::PowerShellExecution(_) => WINCENT_ERR_POWERSHELL_EXECUTION,
+        WincentError::PowerShellNotFound => WINCENT_ERR_POWERSHELL_NOT_FOUND,
+        WincentError::PowerShellSpawn(_) => WINCENT_ERR_POWERSHELL_SPAWN,
+        WincentError::InvalidPath(_) => WINCENT_ERR_INVALID_PATH,
+        WincentError::UnsupportedOperation(_) => WINCENT_ERR_UNSUPPORTED_OPERATION,
+        WincentError::SystemError(_) => WINCENT_ERR_SYSTEM,
+        WincentError::ArrayConversion(_) => WINCENT_ERR_ARRAY_CONVERSION,
+        WincentError::ScriptFailed(_) => WINCENT_ERR_SCRIPT_FAILED,
+        WincentError::UnknownQuickAccessType(_) => WINCENT_ERR_UNKNOWN_QUICK_ACCESS_TYPE,
+        WincentError::UnknownScriptMethod(_) => WINCENT_ERR_UNKNOWN_SCRIPT_METHOD,
+        WincentError::MissingParameter | WincentError::MissingParemeter => {
+            WINCENT_ERR_MISSING_PARAMETER
+        }
+        WincentError::InvalidBackup(_) => WINCENT_ERR_INVALID_BACKUP,
+        WincentError::WindowsApi(_) => WINCENT_ERR_WINDOWS_API,
+        WincentError::Timeout(_) => WINCENT_ERR_TIMEOUT,
+        WincentError::InvalidArgument(_) => WINCENT_ERR_INVALID_ARGUMENT_VALUE,
+        WincentError::AlreadyExists(_) => WINCENT_ERR_ALREADY_EXISTS,
+        WincentError::NotInQuickAccess(_) => WINCENT_ERR_NOT_IN_QUICK_ACCESS,
+        WincentError::ExecutionPolicyRestricted => WINCENT_ERR_EXECUTION_POLICY_RESTRICTED,
+    }
+}
+
+/// Hands a list of strings over to the caller, see the module level ownership rules.
+///
+/// # Safety
+///
+/// `out` and `count` must be valid for writes.
+unsafe fn export_strings(
+    items: WincentResult<Vec<String>>,
+    out: *mut *mut *mut c_char,
+    count: *mut usize,
+) -> i32 {
+    if out.is_null() || count.is_null() {
+        return WINCENT_ERR_INVALID_ARGUMENT;
+    }
+
+    let items = match items {
+        Ok(items) => items,
+        Err(err) => return error_code(&err),
+    };
+
+    let strings: Result<Vec<CString>, _> = items.into_iter().map(CString::new).collect();
+    let Ok(strings) = strings else {
+        return WINCENT_ERR_INVALID_ARGUMENT;
+    };
+
+    *count = strings.len();
+    *out = if strings.is_empty() {
+        std::ptr::null_mut()
+    } else {
+        let raw: Box<[*mut c_char]> = strings.into_iter().map(CString::into_raw).collect();
+        Box::into_raw(raw) as *mut *mut c_char
+    };
+
+    WINCENT_OK
+}
+
+/// Runs an operation on a path passed by the caller.
+///
+/// # Safety
+///
+/// `path` must be null or point to a null-terminated string.
+unsafe fn with_path(path: *const c_char, op: fn(&str) -> WincentResult<()>) -> i32 {
+    if path.is_null() {
+        return WINCENT_ERR_INVALID_ARGUMENT;
+    }
+
+    let Ok(path) = CStr::from_ptr(path).to_str() else {
+        return WINCENT_ERR_INVALID_ARGUMENT;
+    };
+
+    match op(path) {
+        Ok(()) => WINCENT_OK,
+        Err(err) => error_code(&err),
+    }
+}
+
+/// Gets the recent files, see [`crate::query::get_recent_files`].
+///
+/// # Safety
+///
+/// `out` and `count` must be valid for writes. On success `*out` must be released with
+/// [`wincent_free`].
+#[no_mangle]
+pub unsafe extern "C" fn wincent_get_recent_files(
+    out: *mut *mut *mut c_char,
+    count: *mut usize,
+) -> i32 {
+    export_strings(get_recent_files(), out, count)
+}
+
+/// Gets the frequent folders, see [`crate::query::get_frequent_folders`].
+///
+/// # Safety
+///
+/// `out` and `count` must be valid for writes. On success `*out` must be released with
+/// [`wincent_free`].
+#[no_mangle]
+pub unsafe extern "C" fn wincent_get_frequent_folders(
+    out: *mut *mut *mut c_char,
+    count: *mut usize,
+) -> i32 {
+    export_strings(get_frequent_folders(), out, count)
+}
+
+/// Gets all Quick Access items, see [`crate::query::get_quick_access_items`].
+///
+/// # Safety
+///
+/// `out` and `count` must be valid for writes. On success `*out` must be released with
+/// [`wincent_free`].
+#[no_mangle]
+pub unsafe extern "C" fn wincent_get_quick_access_items(
+    out: *mut *mut *mut c_char,
+    count: *mut usize,
+) -> i32 {
+    export_strings(get_quick_access_items(), out, count)
+}
+
+/// Adds a file to the recent files, see [`crate::handle::add_to_recent_files`].
+///
+/// # Safety
+///
+/// `path` must be null or point to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wincent_add_to_recent_files(path: *const c_char) -> i32 {
+    with_path(path, add_to_recent_files)
+}
+
+/// Removes a file from the recent files, see [`crate::handle::remove_from_recent_files`].
+///
+/// # Safety
+///
+/// `path` must be null or point to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wincent_remove_from_recent_files(path: *const c_char) -> i32 {
+    with_path(path, remove_from_recent_files)
+}
+
+/// Pins a folder to Quick Access, see [`crate::handle::add_to_frequent_folders`].
+///
+/// # Safety
+///
+/// `path` must be null or point to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wincent_add_to_frequent_folders(path: *const c_char) -> i32 {
+    with_path(path, add_to_frequent_folders)
+}
+
+/// Unpins a folder from Quick Access, see [`crate::handle::remove_from_frequent_folders`].
+///
+/// # Safety
+///
+/// `path` must be null or point to a null-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn wincent_remove_from_frequent_folders(path: *const c_char) -> i32 {
+    with_path(path, remove_from_frequent_folders)
+}
+
+/// Releases a string array returned by a query function.
+///
+/// # Safety
+///
+/// `items` must be null or an array returned by this library that was not freed yet,
+/// and `count` must be the count returned with it.
+#[no_mangle]
+pub unsafe extern "C" fn wincent_free(items: *mut *mut c_char, count: usize) {
+    if items.is_null() {
+        return;
+    }
+
+    let raw = Box::from_raw(std::ptr::slice_from_raw_parts_mut(items, count));
+    for item in raw.iter() {
+        drop(CString::from_raw(*item));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_and_free_strings() {
+        let items = vec!["C:\\Docs\\a.txt".to_string(), "C:\\文档".to_string()];
+        let mut out: *mut *mut c_char = std::ptr::null_mut();
+        let mut count = 0;
+
+        unsafe {
+            assert_eq!(
+                export_strings(Ok(items.clone()), &mut out, &mut count),
+                WINCENT_OK
+            );
+            assert_eq!(count, items.len());
+
+            let exported: Vec<String> = std::slice::from_raw_parts(out, count)
+                .iter()
+                .map(|item| CStr::from_ptr(*item).to_str().unwrap().to_string())
+                .collect();
+            assert_eq!(exported, items);
+
+            wincent_free(out, count);
+        }
+    }
+
+    #[test]
+    fn test_export_empty_strings() {
+        let mut out: *mut *mut c_char = std::ptr::dangling_mut();
+        let mut count = 1;
+
+        unsafe {
+            assert_eq!(
+                export_strings(Ok(Vec::new()), &mut out, &mut count),
+                WINCENT_OK
+            );
+            assert!(out.is_null(), "Empty result should be a null array");
+            assert_eq!(count, 0);
+            wincent_free(out, count);
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        let mut count = 0;
+
+        unsafe {
+            assert_eq!(
+                export_strings(Ok(Vec::new()), std::ptr::null_mut(), &mut count),
+                WINCENT_ERR_INVALID_ARGUMENT
+            );
+            assert_eq!(
+                wincent_add_to_recent_files(std::ptr::null()),
+                WINCENT_ERR_INVALID_ARGUMENT
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let mut out: *mut *mut c_char = std::ptr::null_mut();
+        let mut count = 0;
+
+        unsafe {
+            assert_eq!(
+                export_strings(
+                    Err(WincentError::InvalidPath("missing".to_string())),
+                    &mut out,
+                    &mut count
+                ),
+                WINCENT_ERR_INVALID_PATH
+            );
+        }
+        assert!(out.is_null(), "Output should be untouched on error");
+
+        assert_eq!(
+            error_code(&WincentError::PowerShellNotFound),
+            WINCENT_ERR_POWERSHELL_NOT_FOUND
+        );
+        assert_eq!(
+            error_code(&WincentError::WindowsApi(5)),
+            WINCENT_ERR_WINDOWS_API
+        );
+        assert_eq!(
+            error_code(&WincentError::NotInQuickAccess("C:\\Gone".to_string())),
+            WINCENT_ERR_NOT_IN_QUICK_ACCESS
+        );
+        assert_eq!(
+            error_code(&WincentError::ExecutionPolicyRestricted),
+            WINCENT_ERR_EXECUTION_POLICY_RESTRICTED
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Read the Quick Access of another user through their access token.
+//!
+//! Only available with the `impersonation` feature. It targets services and other
+//! processes running under a different account than the user whose Quick Access is
+//! managed, for example a service acting on behalf of the logged-in user with a token
+//! obtained from `WTSQueryUserToken`.
+//!
+//! ## Security
+//!
+//! The token gives the calling thread the identity and access rights of its user while
+//! [`with_impersonation`] runs. Only pass tokens of users the caller is meant to act for,
+//! and never tokens received from an untrusted process. If reverting to the process
+//! identity fails afterwards, the process is aborted rather than continuing to run as
+//! the impersonated user.
+//!
+//! Impersonation applies to the calling thread only, and covers file system access
+//! alone. Operations running PowerShell start a new process, which gets the token of the
+//! calling process and not the impersonated one. `HKEY_CURRENT_USER` stays mapped to the
+//! hive of the process user, so registry settings and the recent documents history
+//! `SHAddToRecentDocs` keeps there are not the user's, and the record of items added
+//! through wincent lives in the temporary folder of the process. Modifying the Quick
+//! Access of another user is therefore not supported, only the read-only helpers of this
+//! module, which take the token explicitly, act as that user.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use wincent::{impersonation::get_recent_items_for_user, WincentResult};
+//! use windows::Win32::Foundation::HANDLE;
+//!
+//! fn print_user_recent_items(token: HANDLE) -> WincentResult<()> {
+//!     for item in get_recent_items_for_user(token)? {
+//!         println!("{}", item);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{query::list_shortcut_targets, utils::get_known_folder_path_with_token, WincentResult};
+use std::path::Path;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Security::{ImpersonateLoggedOnUser, RevertToSelf};
+use windows::Win32::UI::Shell::FOLDERID_Recent;
+
+/// Reverts the calling thread to the process identity when dropped.
+struct RevertGuard;
+
+impl Drop for RevertGuard {
+    fn drop(&mut self) {
+        if unsafe { RevertToSelf() }.is_err() {
+            // Carrying on would leave the thread running with the user's rights
+            std::process::abort();
+        }
+    }
+}
+
+/// Gets the path of the Recent folder of the user an access token belongs to.
+///
+/// # Arguments
+///
+/// * `token` - Access token of the user, with `TOKEN_QUERY` and `TOKEN_IMPERSONATE`
+///   access. A null handle stands for the current user.
+///
+/// # Returns
+///
+/// Returns the full path of the user's Recent folder.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{impersonation::get_recent_folder_for_user, error::WincentError};
+/// use windows::Win32::Foundation::HANDLE;
+///
+/// fn main() -> Result<(), WincentError> {
+///     let token = HANDLE(std::ptr::null_mut());
+///     println!("Recent folder: {}", get_recent_folder_for_user(token)?);
+///     Ok(())
+/// }
+/// ```
+pub fn get_recent_folder_for_user(token: HANDLE) -> WincentResult<String> {
+    get_known_folder_path_with_token(&FOLDERID_Recent, token)
+}
+
+/// Gets the recent items of the user an access token belongs to, most recent first.
+///
+/// Items are read from the shortcuts in the user's Recent folder, without running
+/// PowerShell, so the result does not depend on the identity scripts would run as.
+/// Shortcuts to virtual shell items are skipped.
+///
+/// # Arguments
+///
+/// * `token` - Access token of the user, see [`get_recent_folder_for_user`]
+///
+/// # Returns
+///
+/// Returns the target paths of the user's recent items.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{impersonation::get_recent_items_for_user, error::WincentError};
+/// use windows::Win32::Foundation::HANDLE;
+///
+/// fn main() -> Result<(), WincentError> {
+///     let items = get_recent_items_for_user(HANDLE(std::ptr::null_mut()))?;
+///     println!("{} recent items", items.len());
+///     Ok(())
+/// }
+/// ```
+pub fn get_recent_items_for_user(token: HANDLE) -> WincentResult<Vec<String>> {
+    let folder = get_recent_folder_for_user(token)?;
+
+    list_shortcut_targets(Path::new(&folder))
+}
+
+/// Runs a closure on the calling thread while impersonating the user of an access token.
+///
+/// The thread reverts to the process identity when the closure returns or panics. Files
+/// are accessed with the rights of the user, the registry and PowerShell based operations
+/// are not, see the [module documentation](self).
+///
+/// # Arguments
+///
+/// * `token` - Access token of the user, with `TOKEN_QUERY` and `TOKEN_DUPLICATE` access
+/// * `operation` - The operation to run as the user
+///
+/// # Returns
+///
+/// Returns the result of the closure, or an error if the user could not be impersonated.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{impersonation::with_impersonation, error::WincentError};
+/// use windows::Win32::Foundation::HANDLE;
+///
+/// fn user_can_open(token: HANDLE, path: &str) -> Result<bool, WincentError> {
+///     // Only the user's own rights count, for example on a share mapped for them
+///     with_impersonation(token, || Ok(std::fs::File::open(path).is_ok()))
+/// }
+/// ```
+pub fn with_impersonation<T, F>(token: HANDLE, operation: F) -> WincentResult<T>
+where
+    F: FnOnce() -> WincentResult<T>,
+{
+    unsafe { ImpersonateLoggedOnUser(token) }?;
+    let _revert = RevertGuard;
+
+    operation()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::get_windows_recent_folder;
+
+    #[test]
+    fn test_null_token_is_current_user() -> WincentResult<()> {
+        let token = HANDLE(std::ptr::null_mut());
+        assert_eq!(
+            get_recent_folder_for_user(token)?,
+            get_windows_recent_folder()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_impersonation_rejects_invalid_token() {
+        let result = with_impersonation(HANDLE(std::ptr::null_mut()), || Ok(()));
+        assert!(result.is_err(), "A null token cannot be impersonated");
+    }
+}
@@ -18,6 +18,10 @@
 //!   - Show/Hide recent files
 //!   - Show/Hide frequent folders
 //!
+//! - Backup and Restore
+//!   - Back up items before clearing them
+//!   - Restore items from a backup file
+//!
 //! ## Basic Example
 //!
 //! ```rust
@@ -78,18 +82,66 @@
 //! - Windows API integration
 //! - Cross-version Windows support
 //!
+//! ## Concurrency
+//!
+//! All functions can be called from several threads. Each individual change to Quick
+//! Access (adding, removing, pinning, clearing or changing visibility) is serialized
+//! process-wide, while queries run concurrently. Operations made of several changes,
+//! such as [`handle::set_pinned_folders`], are not atomic as a whole.
+//!
+//! ## Optional Features
+//!
+//! - `ffi`: C-compatible bindings in the [`ffi`] module
+//!
+//! ## Platform Support
+//!
+//! Only Windows targets are supported. Building for any other target stops with a
+//! `compile_error!` explaining how to declare the dependency as Windows-only.
+//!
+
+#[cfg(not(windows))]
+compile_error!(
+    "wincent only supports Windows targets, declare it under `[target.'cfg(windows)'.dependencies]` in cross-platform workspaces"
+);
 
+#[cfg(windows)]
+pub mod backup;
+#[cfg(windows)]
+pub mod config;
+#[cfg(windows)]
+pub mod custom;
+#[cfg(windows)]
 pub mod empty;
 pub mod error;
+#[cfg(windows)]
 pub mod feasible;
+#[cfg(all(windows, feature = "ffi"))]
+pub mod ffi;
+#[cfg(windows)]
 pub mod handle;
+#[cfg(all(windows, feature = "impersonation"))]
+pub mod impersonation;
+#[cfg(windows)]
+pub mod managed;
+#[cfg(windows)]
+pub mod metrics;
+#[cfg(windows)]
 pub mod query;
+#[cfg(windows)]
 mod scripts;
+#[cfg(windows)]
+pub mod shell;
+#[cfg(windows)]
+pub mod system;
+#[cfg(windows)]
 mod test_utils;
-mod utils;
+#[cfg(windows)]
+pub mod utils;
+#[cfg(windows)]
 pub mod visible;
+#[cfg(windows)]
 #[allow(unused)]
-pub mod predule {
+pub mod prelude {
     pub use crate::empty::{empty_frequent_folders, empty_quick_access, empty_recent_files};
     pub use crate::feasible::{
         check_feasible, check_pinunpin_feasible, check_query_feasible, check_script_feasible,
@@ -104,15 +156,76 @@ pub mod predule {
         is_frequent_folders_visible, is_recent_files_visiable, set_frequent_folders_visiable,
         set_recent_files_visiable,
     };
-    pub use crate::WincentResult;
+    pub use crate::{QuickAccess, WincentResult};
+}
+#[cfg(windows)]
+#[deprecated(since = "0.1.2", note = "misspelled, use `wincent::prelude` instead")]
+pub mod predule {
+    pub use crate::prelude::*;
 }
 
 use crate::error::WincentError;
 
-pub(crate) enum QuickAccess {
+/// Categories of Windows Quick Access items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAccess {
+    /// Folders pinned to or frequently used in Quick Access.
     FrequentFolders,
+    /// Files recently opened by the user.
     RecentFiles,
+    /// Both recent files and frequent folders.
     All,
 }
 
+impl QuickAccess {
+    /// Returns the stable integer representation used for FFI and serialization.
+    ///
+    /// `0` is [`QuickAccess::RecentFiles`], `1` is [`QuickAccess::FrequentFolders`]
+    /// and `2` is [`QuickAccess::All`].
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            QuickAccess::RecentFiles => 0,
+            QuickAccess::FrequentFolders => 1,
+            QuickAccess::All => 2,
+        }
+    }
+}
+
+impl TryFrom<u32> for QuickAccess {
+    type Error = WincentError;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(QuickAccess::RecentFiles),
+            1 => Ok(QuickAccess::FrequentFolders),
+            2 => Ok(QuickAccess::All),
+            _ => Err(WincentError::UnknownQuickAccessType(value)),
+        }
+    }
+}
+
 pub type WincentResult<T> = Result<T, WincentError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quick_access_u32_round_trip() {
+        for qa_type in [
+            QuickAccess::RecentFiles,
+            QuickAccess::FrequentFolders,
+            QuickAccess::All,
+        ] {
+            assert_eq!(QuickAccess::try_from(qa_type.as_u32()).unwrap(), qa_type);
+        }
+    }
+
+    #[test]
+    fn test_quick_access_from_unknown_u32() {
+        assert!(matches!(
+            QuickAccess::try_from(3),
+            Err(WincentError::UnknownQuickAccessType(3))
+        ));
+    }
+}
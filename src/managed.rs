@@ -0,0 +1,470 @@
+//! Track the Quick Access items added through wincent.
+//!
+//! Every file added with [`crate::handle::add_to_recent_files`] and every folder pinned
+//! with [`crate::handle::add_to_frequent_folders`], including through the functions built
+//! on them, is recorded in a sidecar file in the `wincent` temporary folder. Items added
+//! by the user or applications not using wincent are not recorded, so tools can manage
+//! their own items without disturbing the others.
+//!
+//! The record is kept per user. Applications using wincent share it unless they set
+//! [`crate::config::WincentConfig::app_id`], which gives each of them its own record.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use wincent::{
+//!     config::{get_config, set_config},
+//!     handle::add_to_frequent_folders,
+//!     managed::list_managed,
+//!     QuickAccess, WincentResult,
+//! };
+//!
+//! fn main() -> WincentResult<()> {
+//!     let mut config = get_config();
+//!     config.app_id = Some("MyCompany.ProjectTool".to_string());
+//!     set_config(config);
+//!
+//!     add_to_frequent_folders("C:\\Projects\\my-project")?;
+//!
+//!     for folder in list_managed(QuickAccess::FrequentFolders)? {
+//!         println!("Pinned by this application: {}", folder);
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    backup::{FREQUENT_TAG, RECENT_TAG},
+    config::get_config,
+    error::WincentError,
+    handle::{remove_recent_files_with_ps_script, unpin_frequent_folder_with_ps_script},
+    query::query_recent_with_ps_script,
+    utils::paths_equal,
+    QuickAccess, WincentResult,
+};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const MANAGED_HEADER: &str = "# wincent managed items";
+const MANAGED_DIR: &str = "wincent";
+const MANAGED_FILE: &str = "managed.txt";
+const MANAGED_FILE_PREFIX: &str = "managed-";
+
+/// Serializes access to the sidecar file within the process.
+static MANAGED_LOCK: Mutex<()> = Mutex::new(());
+
+/// Items recorded in the sidecar file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ManagedItems {
+    pub(crate) recent_files: Vec<String>,
+    pub(crate) frequent_folders: Vec<String>,
+}
+
+impl ManagedItems {
+    /// Gets the recorded items of a category, [`QuickAccess::All`] gives both.
+    pub(crate) fn items(&self, qa_type: QuickAccess) -> Vec<String> {
+        match qa_type {
+            QuickAccess::RecentFiles => self.recent_files.clone(),
+            QuickAccess::FrequentFolders => self.frequent_folders.clone(),
+            QuickAccess::All => [self.frequent_folders.clone(), self.recent_files.clone()].concat(),
+        }
+    }
+
+    /// Gets the recorded list of a category, `None` for [`QuickAccess::All`].
+    fn list_mut(&mut self, qa_type: QuickAccess) -> Option<&mut Vec<String>> {
+        match qa_type {
+            QuickAccess::RecentFiles => Some(&mut self.recent_files),
+            QuickAccess::FrequentFolders => Some(&mut self.frequent_folders),
+            QuickAccess::All => None,
+        }
+    }
+
+    /// Records an item, unless the same path is already recorded for the category.
+    pub(crate) fn insert(&mut self, qa_type: QuickAccess, path: &str) {
+        if let Some(items) = self.list_mut(qa_type) {
+            if !items.iter().any(|item| paths_equal(item, path)) {
+                items.push(path.to_string());
+            }
+        }
+    }
+
+    /// Forgets a recorded item of a category.
+    pub(crate) fn remove(&mut self, qa_type: QuickAccess, path: &str) {
+        if let Some(items) = self.list_mut(qa_type) {
+            items.retain(|item| !paths_equal(item, path));
+        }
+    }
+
+    /// Drops the recorded items of a category that are not in `present` anymore.
+    pub(crate) fn retain_present(&mut self, qa_type: QuickAccess, present: &[String]) {
+        if let Some(items) = self.list_mut(qa_type) {
+            items.retain(|item| present.iter().any(|p| paths_equal(p, item)));
+        }
+    }
+
+    /// Serializes the items, one tagged path per line like backups.
+    pub(crate) fn to_text(&self) -> String {
+        let mut lines = vec![MANAGED_HEADER.to_string()];
+        lines.extend(
+            self.recent_files
+                .iter()
+                .map(|path| format!("{}\t{}", RECENT_TAG, path)),
+        );
+        lines.extend(
+            self.frequent_folders
+                .iter()
+                .map(|path| format!("{}\t{}", FREQUENT_TAG, path)),
+        );
+
+        lines.join("\n") + "\n"
+    }
+
+    /// Parses the items, skipping lines that are not recognized.
+    pub(crate) fn from_text(text: &str) -> Self {
+        let mut managed = ManagedItems::default();
+
+        for line in text.lines().filter(|line| !line.starts_with('#')) {
+            match line.split_once('\t') {
+                Some((RECENT_TAG, path)) => managed.recent_files.push(path.to_string()),
+                Some((FREQUENT_TAG, path)) => managed.frequent_folders.push(path.to_string()),
+                _ => {}
+            }
+        }
+
+        managed
+    }
+}
+
+/// Gets the path of the sidecar file of the configured application.
+pub(crate) fn managed_file() -> PathBuf {
+    std::env::temp_dir()
+        .join(MANAGED_DIR)
+        .join(managed_file_name(get_config().app_id.as_deref()))
+}
+
+/// Gets the sidecar file name of an application, characters not allowed in file names
+/// are replaced by `_`.
+pub(crate) fn managed_file_name(app_id: Option<&str>) -> String {
+    match app_id.filter(|id| !id.is_empty()) {
+        Some(id) => {
+            let id: String = id
+                .chars()
+                .map(|c| match c {
+                    '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*' => '_',
+                    c if c.is_control() => '_',
+                    c => c,
+                })
+                .collect();
+            format!("{}{}.txt", MANAGED_FILE_PREFIX, id)
+        }
+        None => MANAGED_FILE.to_string(),
+    }
+}
+
+/// Reads the sidecar file, a missing file records nothing.
+pub(crate) fn read_managed(path: &Path) -> WincentResult<ManagedItems> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(ManagedItems::from_text(&text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(ManagedItems::default()),
+        Err(e) => Err(WincentError::Io(e)),
+    }
+}
+
+/// Writes the sidecar file, creating its folder if needed.
+pub(crate) fn write_managed(path: &Path, managed: &ManagedItems) -> WincentResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    std::fs::write(path, managed.to_text()).map_err(WincentError::Io)
+}
+
+/// Records an item added through wincent.
+///
+/// Failing to record does not undo the addition, the item is only left untracked.
+pub(crate) fn record_managed(qa_type: QuickAccess, path: &str) {
+    let _lock = MANAGED_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sidecar = managed_file();
+
+    let result = read_managed(&sidecar).and_then(|mut managed| {
+        managed.insert(qa_type, path);
+        write_managed(&sidecar, &managed)
+    });
+
+    if let Err(e) = result {
+        log::warn!("Failed to record managed item {}: {}", path, e);
+    }
+}
+
+/// Lists the items added through wincent that are still in Quick Access.
+///
+/// Recorded items that were removed since, by wincent, the user or another application,
+/// are dropped from the record.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category, [`QuickAccess::All`] lists both
+///
+/// # Returns
+///
+/// Returns the managed item paths, in the order they were first added.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{managed::list_managed, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let files = list_managed(QuickAccess::RecentFiles)?;
+///     println!("{} recent files were added through wincent", files.len());
+///     Ok(())
+/// }
+/// ```
+pub fn list_managed(qa_type: QuickAccess) -> WincentResult<Vec<String>> {
+    let categories = match qa_type {
+        QuickAccess::All => vec![QuickAccess::FrequentFolders, QuickAccess::RecentFiles],
+        category => vec![category],
+    };
+
+    let _lock = MANAGED_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sidecar = managed_file();
+    let mut managed = read_managed(&sidecar)?;
+    let recorded = managed.clone();
+
+    for category in categories {
+        if managed.items(category).is_empty() {
+            continue;
+        }
+        let present = query_recent_with_ps_script(category)?;
+        managed.retain_present(category, &present);
+    }
+
+    if managed != recorded {
+        write_managed(&sidecar, &managed)?;
+    }
+
+    Ok(managed.items(qa_type))
+}
+
+/// Removes the recorded items one by one, folders first, dropping each from `managed`
+/// once it is gone.
+///
+/// Items already gone are dropped without being reported. On error, `managed` keeps the
+/// items not removed yet.
+pub(crate) fn remove_managed_with<F>(
+    managed: &mut ManagedItems,
+    mut remove: F,
+) -> WincentResult<Vec<String>>
+where
+    F: FnMut(QuickAccess, &str) -> WincentResult<()>,
+{
+    let mut removed = Vec::new();
+
+    for qa_type in [QuickAccess::FrequentFolders, QuickAccess::RecentFiles] {
+        while let Some(item) = managed.items(qa_type).into_iter().next() {
+            let was_listed = match remove(qa_type, &item) {
+                Ok(()) => true,
+                Err(WincentError::NotInQuickAccess(_)) => false,
+                Err(e) => return Err(e),
+            };
+            managed.remove(qa_type, &item);
+            if was_listed {
+                removed.push(item);
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Removes every item added through wincent and clears the record.
+///
+/// Folders are unpinned and files are removed from Recent Files. Items already removed
+/// by the user or another application are skipped. Items never added through wincent are
+/// left untouched, unlike [`crate::empty::empty_quick_access`].
+///
+/// # Returns
+///
+/// Returns the removed item paths.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{managed::remove_all_managed, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     // Undo everything the application added, for example when uninstalling
+///     let removed = remove_all_managed()?;
+///     println!("Removed {} items", removed.len());
+///     Ok(())
+/// }
+/// ```
+pub fn remove_all_managed() -> WincentResult<Vec<String>> {
+    let _lock = MANAGED_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let sidecar = managed_file();
+    let mut managed = read_managed(&sidecar)?;
+
+    let result = remove_managed_with(&mut managed, |qa_type, item| match qa_type {
+        QuickAccess::RecentFiles => remove_recent_files_with_ps_script(item),
+        _ => unpin_frequent_folder_with_ps_script(item),
+    });
+
+    // Keep the items that could not be removed for the next attempt
+    write_managed(&sidecar, &managed)?;
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_managed_items_text_round_trip() {
+        let mut managed = ManagedItems::default();
+        managed.insert(QuickAccess::RecentFiles, "C:\\Docs\\report.docx");
+        managed.insert(QuickAccess::FrequentFolders, "C:\\Projects");
+        managed.insert(QuickAccess::FrequentFolders, "c:/projects/");
+        managed.insert(QuickAccess::All, "C:\\Ignored");
+
+        assert_eq!(managed.frequent_folders, vec!["C:\\Projects".to_string()]);
+        assert_eq!(ManagedItems::from_text(&managed.to_text()), managed);
+        assert_eq!(
+            managed.items(QuickAccess::All),
+            vec![
+                "C:\\Projects".to_string(),
+                "C:\\Docs\\report.docx".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_managed_file_name() {
+        assert_eq!(managed_file_name(None), MANAGED_FILE);
+        assert_eq!(managed_file_name(Some("")), MANAGED_FILE);
+        assert_eq!(
+            managed_file_name(Some("MyCompany.Tool")),
+            "managed-MyCompany.Tool.txt"
+        );
+        assert_eq!(
+            managed_file_name(Some("a/b\\c:d")),
+            "managed-a_b_c_d.txt",
+            "Separators should not escape the wincent folder"
+        );
+    }
+
+    #[test]
+    fn test_managed_items_retain_present() {
+        let mut managed = ManagedItems::default();
+        managed.insert(QuickAccess::FrequentFolders, "C:\\Projects");
+        managed.insert(QuickAccess::FrequentFolders, "C:\\Unpinned");
+        managed.insert(QuickAccess::RecentFiles, "C:\\Docs\\a.txt");
+
+        managed.retain_present(
+            QuickAccess::FrequentFolders,
+            &["c:\\projects".to_string(), "C:\\Users".to_string()],
+        );
+
+        assert_eq!(managed.frequent_folders, vec!["C:\\Projects".to_string()]);
+        assert_eq!(managed.recent_files.len(), 1, "Other categories are kept");
+    }
+
+    #[test]
+    fn test_remove_managed_with() {
+        let mut managed = ManagedItems::default();
+        managed.insert(QuickAccess::RecentFiles, "C:\\Docs\\a.txt");
+        managed.insert(QuickAccess::FrequentFolders, "C:\\Projects");
+        managed.insert(QuickAccess::FrequentFolders, "C:\\Gone");
+
+        let mut calls = Vec::new();
+        let removed = remove_managed_with(&mut managed, |qa_type, item| {
+            calls.push((qa_type, item.to_string()));
+            match item {
+                "C:\\Gone" => Err(WincentError::NotInQuickAccess(item.to_string())),
+                _ => Ok(()),
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            removed,
+            vec!["C:\\Projects".to_string(), "C:\\Docs\\a.txt".to_string()]
+        );
+        assert_eq!(calls.len(), 3);
+        assert_eq!(managed, ManagedItems::default());
+    }
+
+    #[test]
+    fn test_remove_managed_with_keeps_failed_items() {
+        let mut managed = ManagedItems::default();
+        managed.insert(QuickAccess::FrequentFolders, "C:\\Projects");
+        managed.insert(QuickAccess::FrequentFolders, "C:\\Locked");
+        managed.insert(QuickAccess::RecentFiles, "C:\\Docs\\a.txt");
+
+        let result = remove_managed_with(&mut managed, |_, item| match item {
+            "C:\\Locked" => Err(WincentError::ScriptFailed("denied".to_string())),
+            _ => Ok(()),
+        });
+
+        assert!(matches!(result, Err(WincentError::ScriptFailed(_))));
+        assert_eq!(managed.frequent_folders, vec!["C:\\Locked".to_string()]);
+        assert_eq!(managed.recent_files.len(), 1);
+    }
+
+    #[test]
+    #[ignore]
+    fn test_remove_all_managed() -> WincentResult<()> {
+        use crate::handle::add_to_frequent_folders;
+        use crate::test_utils::{cleanup_test_env, setup_test_env};
+
+        let test_dir = setup_test_env()?;
+        let first = test_dir.join("managed_first");
+        let second = test_dir.join("managed_second");
+        std::fs::create_dir(&first)?;
+        std::fs::create_dir(&second)?;
+
+        add_to_frequent_folders(first.to_str().unwrap())?;
+        add_to_frequent_folders(second.to_str().unwrap())?;
+        std::thread::sleep(std::time::Duration::from_secs(1));
+
+        let listed = list_managed(QuickAccess::FrequentFolders)?;
+        assert!(listed
+            .iter()
+            .any(|item| paths_equal(item, first.to_str().unwrap())));
+        assert!(listed
+            .iter()
+            .any(|item| paths_equal(item, second.to_str().unwrap())));
+
+        let removed = remove_all_managed()?;
+        assert!(removed
+            .iter()
+            .any(|item| paths_equal(item, first.to_str().unwrap())));
+        assert!(removed
+            .iter()
+            .any(|item| paths_equal(item, second.to_str().unwrap())));
+        assert!(list_managed(QuickAccess::All)?.is_empty());
+
+        let folders = query_recent_with_ps_script(QuickAccess::FrequentFolders)?;
+        assert!(!folders
+            .iter()
+            .any(|item| paths_equal(item, first.to_str().unwrap())));
+
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_write_managed() -> WincentResult<()> {
+        let dir = tempfile::tempdir()?;
+        let sidecar = dir.path().join("nested").join(MANAGED_FILE);
+
+        assert_eq!(read_managed(&sidecar)?, ManagedItems::default());
+
+        let mut managed = ManagedItems::default();
+        managed.insert(QuickAccess::RecentFiles, "C:\\Docs\\a.txt");
+        write_managed(&sidecar, &managed)?;
+        assert_eq!(read_managed(&sidecar)?, managed);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Collect timing and outcome metrics for Quick Access operations.
+//!
+//! Every PowerShell script run by the crate, whether it queries or modifies Quick Access,
+//! is reported to the registered sink. When no sink is registered, no timing is taken.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//! use wincent::{
+//!     metrics::{set_metrics, Metrics},
+//!     query::get_recent_files,
+//!     WincentResult,
+//! };
+//!
+//! struct StdoutMetrics;
+//!
+//! impl Metrics for StdoutMetrics {
+//!     fn record(&self, op: &str, duration: Duration, success: bool) {
+//!         println!("{} took {:?} (success: {})", op, duration, success);
+//!     }
+//! }
+//!
+//! fn main() -> WincentResult<()> {
+//!     set_metrics(Arc::new(StdoutMetrics));
+//!     get_recent_files()?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+/// A sink receiving one record per executed operation.
+pub trait Metrics: Send + Sync {
+    /// Records the name, duration and outcome of an operation.
+    fn record(&self, op: &str, duration: Duration, success: bool);
+}
+
+static METRICS_ENABLED: AtomicBool = AtomicBool::new(false);
+static METRICS_SINK: RwLock<Option<Arc<dyn Metrics>>> = RwLock::new(None);
+
+/// Starts timing an operation, returns `None` when no sink is registered.
+pub(crate) fn start() -> Option<Instant> {
+    METRICS_ENABLED.load(Ordering::Acquire).then(Instant::now)
+}
+
+/// Reports a finished operation to the registered sink.
+pub(crate) fn finish(op: &str, started: Option<Instant>, success: bool) {
+    let Some(started) = started else {
+        return;
+    };
+
+    let sink = match METRICS_SINK.read() {
+        Ok(guard) => guard.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+
+    if let Some(sink) = sink {
+        sink.record(op, started.elapsed(), success);
+    }
+}
+
+/// Registers the sink receiving operation metrics, replacing any previous one.
+///
+/// # Arguments
+///
+/// * `sink` - The metrics sink shared by all threads of the process
+///
+/// # Example
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use wincent::metrics::{set_metrics, Metrics};
+///
+/// struct Silent;
+///
+/// impl Metrics for Silent {
+///     fn record(&self, _op: &str, _duration: Duration, _success: bool) {}
+/// }
+///
+/// set_metrics(Arc::new(Silent));
+/// ```
+pub fn set_metrics(sink: Arc<dyn Metrics>) {
+    let mut guard = match METRICS_SINK.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *guard = Some(sink);
+    METRICS_ENABLED.store(true, Ordering::Release);
+}
+
+/// Removes the registered metrics sink, disabling metrics collection.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::metrics::clear_metrics;
+///
+/// clear_metrics();
+/// ```
+pub fn clear_metrics() {
+    let mut guard = match METRICS_SINK.write() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    METRICS_ENABLED.store(false, Ordering::Release);
+    *guard = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingMetrics {
+        records: Mutex<Vec<(String, bool)>>,
+    }
+
+    impl Metrics for RecordingMetrics {
+        fn record(&self, op: &str, _duration: Duration, success: bool) {
+            self.records.lock().unwrap().push((op.to_string(), success));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_no_timing_without_sink() {
+        clear_metrics();
+        assert!(start().is_none(), "Should not time without a sink");
+    }
+
+    #[test]
+    #[serial]
+    fn test_records_reach_sink() {
+        let sink = Arc::new(RecordingMetrics::default());
+        set_metrics(sink.clone());
+
+        let started = start();
+        assert!(started.is_some(), "Should time with a sink registered");
+        finish("test_query", started, true);
+        finish("test_pin", start(), false);
+
+        clear_metrics();
+        finish("test_ignored", Some(Instant::now()), true);
+
+        // Scripts run by concurrent tests may be recorded as well, only check ours
+        let records: Vec<_> = sink
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(op, _)| op.starts_with("test_"))
+            .cloned()
+            .collect();
+        assert_eq!(
+            records,
+            vec![
+                ("test_query".to_string(), true),
+                ("test_pin".to_string(), false),
+            ]
+        );
+    }
+}
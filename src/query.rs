@@ -72,27 +72,120 @@
 //! ```
 
 use crate::{
+    config::get_config,
     error::WincentError,
     feasible::{check_query_feasible, check_script_feasible},
-    scripts::{execute_ps_script, Script},
+    handle::validate_entry_path,
+    scripts::{
+        execute_ps_script, execute_ps_script_with_timeout, Script, FREQUENT_FOLDERS_NAMESPACE,
+    },
+    utils::{
+        self, get_jumplist_modified, get_known_folder_path, get_quick_access_data_files,
+        get_windows_recent_folder, is_path_under, normalize_path, paths_equal, resolve_shortcut,
+        resolve_shortcut_with_api, ComGuard, MAX_JUMPLIST_SIZE,
+    },
+    visible::{is_frequent_folders_visible, is_recent_files_visiable},
     QuickAccess, WincentResult,
 };
+use std::collections::{HashMap, VecDeque};
+use std::io::Read;
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use windows::core::{HSTRING, PSTR};
+use windows::Win32::System::Com::{CoTaskMemFree, IBindCtx};
+use windows::Win32::UI::Shell::{
+    BHID_EnumItems, BHID_SFUIObject, FOLDERID_Links, IContextMenu, IEnumShellItems, IShellItem,
+    SHCreateItemFromParsingName, CMF_NORMAL, GCS_VERBW, SIGDN_DESKTOPABSOLUTEPARSING,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreatePopupMenu, DestroyMenu, GetMenuItemCount, GetMenuItemID, HMENU,
+};
 
-/// Queries recent items from Quick Access using a PowerShell script.
-pub(crate) fn query_recent_with_ps_script(qa_type: QuickAccess) -> WincentResult<Vec<String>> {
-    let output = match qa_type {
-        QuickAccess::All => execute_ps_script(Script::QueryQuickAccess, None)?,
-        QuickAccess::RecentFiles => execute_ps_script(Script::QuertRecentFile, None)?,
-        QuickAccess::FrequentFolders => execute_ps_script(Script::QueryFrequentFolder, None)?,
-    };
+/// Items returned by a query that may have fallen back to reading shortcut files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryOutcome {
+    /// The Quick Access item paths.
+    pub items: Vec<String>,
+    /// `true` if the Shell query failed and the items are a best-effort read of the
+    /// shortcut files on disk instead.
+    pub degraded: bool,
+}
+
+/// Health of the frequent folders jump list file backing Quick Access.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JumplistHealth {
+    /// The file does not exist, is empty or is shorter than a compound document header,
+    /// Windows fills it on the next folder access.
+    Missing,
+    /// The file has a valid OLE compound document header.
+    Valid,
+    /// The file exists but is not a valid OLE compound document, or is too large to be one.
+    Corrupt,
+}
+
+/// A frequent folder tagged with whether it is pinned, see [`get_frequent_folders_ordered`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FolderEntry {
+    /// The folder path.
+    pub path: String,
+    /// `true` if the folder is pinned to Quick Access, `false` if it is only listed
+    /// because it is often visited.
+    pub pinned: bool,
+}
+
+/// Quick Access items split by whether their paths answered in time, see
+/// [`get_quick_access_items_reachable`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Reachability {
+    /// Items whose paths exist, in Quick Access order.
+    pub reachable: Vec<String>,
+    /// Items whose paths are missing or did not answer within the probe timeout.
+    pub unreachable: Vec<String>,
+}
 
+/// Changes of the Quick Access items between two state versions, see [`items_changed_since`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ItemDiff {
+    /// The state version the diff leads to, to pass to the next call.
+    pub version: u64,
+    /// Items listed now that were not listed before, in Quick Access order.
+    pub added: Vec<String>,
+    /// Items listed before that are not listed anymore.
+    pub removed: Vec<String>,
+}
+
+const COMPOUND_HEADER_SIZE: usize = 512;
+const COMPOUND_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Command identifiers handed to context menu handlers, `0` stands for no command.
+const FIRST_MENU_COMMAND: u32 = 1;
+const LAST_MENU_COMMAND: u32 = 0x7FFF;
+
+/// Checks whether bytes start with a valid OLE compound document header.
+pub(crate) fn is_compound_header(bytes: &[u8]) -> bool {
+    if bytes.len() < COMPOUND_HEADER_SIZE || bytes[..8] != COMPOUND_SIGNATURE {
+        return false;
+    }
+
+    // Byte order mark is always little endian, sectors are 512 (v3) or 4096 (v4) bytes
+    let byte_order = u16::from_le_bytes([bytes[28], bytes[29]]);
+    let sector_shift = u16::from_le_bytes([bytes[30], bytes[31]]);
+
+    byte_order == 0xFFFE && matches!(sector_shift, 9 | 12)
+}
+
+/// Collects the non-empty output lines of a successful script, or its error output.
+///
+/// Only line endings are stripped, spaces are kept since they can be part of a path.
+pub(crate) fn parse_output_to_strings(output: std::process::Output) -> WincentResult<Vec<String>> {
     if output.status.success() {
         let stdout_str = String::from_utf8(output.stdout).map_err(WincentError::Utf8)?;
 
+        // `lines` strips both LF and CRLF endings
         let data: Vec<String> = stdout_str
             .lines()
-            .map(str::trim)
-            .filter(|line| !line.is_empty())
+            .filter(|line| !line.trim().is_empty())
             .map(String::from)
             .collect();
 
@@ -103,6 +196,165 @@ pub(crate) fn query_recent_with_ps_script(qa_type: QuickAccess) -> WincentResult
     }
 }
 
+/// Prefixes of virtual shell items that can be listed in Quick Access.
+const VIRTUAL_ITEM_PREFIXES: [&str; 3] = ["::{", "shell:", "knownfolder:"];
+
+/// Checks whether a line starts with a URL scheme such as `https://`.
+///
+/// Cloud documents, for example on SharePoint or OneDrive, are listed by their URL.
+fn has_url_scheme(line: &str) -> bool {
+    line.split_once("://").is_some_and(|(scheme, _)| {
+        // A single letter would be a drive, not a scheme
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+/// Checks whether an output line looks like a Quick Access entry rather than a message.
+pub(crate) fn is_path_like(line: &str) -> bool {
+    let lowercase = line.to_lowercase();
+
+    !is_virtual_path(line)
+        || has_url_scheme(line)
+        || VIRTUAL_ITEM_PREFIXES
+            .iter()
+            .any(|prefix| lowercase.starts_with(prefix))
+}
+
+/// Collects the entries listed by a successful query script.
+///
+/// Unless disabled in the configuration, lines that do not look like a path, a URL or
+/// a virtual shell item are dropped, since PowerShell may print banners or warnings
+/// to the standard output on some systems.
+pub(crate) fn parse_output_to_paths(output: std::process::Output) -> WincentResult<Vec<String>> {
+    let lines = parse_output_to_strings(output)?;
+
+    if !get_config().filter_query_output {
+        return Ok(lines);
+    }
+
+    Ok(lines
+        .into_iter()
+        .filter(|line| is_path_like(line))
+        .collect())
+}
+
+/// Queries recent items from Quick Access using a PowerShell script.
+pub(crate) fn query_recent_with_ps_script(qa_type: QuickAccess) -> WincentResult<Vec<String>> {
+    let output = match qa_type {
+        QuickAccess::All => execute_ps_script(Script::QueryQuickAccess, None)?,
+        QuickAccess::RecentFiles => execute_ps_script(Script::QuertRecentFile, None)?,
+        QuickAccess::FrequentFolders => execute_ps_script(Script::QueryFrequentFolder, None)?,
+    };
+
+    parse_output_to_paths(output)
+}
+
+/// Queries recent items from Quick Access, failing if the script runs longer than `timeout`.
+pub(crate) fn query_recent_with_timeout(
+    qa_type: QuickAccess,
+    timeout: Duration,
+) -> WincentResult<Vec<String>> {
+    let script = match qa_type {
+        QuickAccess::All => Script::QueryQuickAccess,
+        QuickAccess::RecentFiles => Script::QuertRecentFile,
+        QuickAccess::FrequentFolders => Script::QueryFrequentFolder,
+    };
+
+    parse_output_to_paths(execute_ps_script_with_timeout(script, None, timeout)?)
+}
+
+/// Queries pinned folders from Quick Access using a PowerShell script.
+pub(crate) fn query_pinned_with_ps_script() -> WincentResult<Vec<String>> {
+    let output = execute_ps_script(Script::QueryPinnedFolder, None)?;
+
+    parse_output_to_paths(output)
+}
+
+/// A `.lnk` shortcut file and the item it points to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ShortcutEntry {
+    /// Path of the shortcut file itself.
+    pub(crate) lnk_path: String,
+    /// Path of the shortcut target.
+    pub(crate) target: String,
+    /// Last modification time of the shortcut, updated whenever the target is reopened.
+    pub(crate) modified: SystemTime,
+}
+
+/// Lists the `.lnk` shortcuts in a folder with filesystem targets, most recently used first.
+pub(crate) fn list_shortcuts(folder: &Path) -> WincentResult<Vec<ShortcutEntry>> {
+    let mut shortcuts: Vec<(SystemTime, String)> = Vec::new();
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+        let is_shortcut = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"));
+        if !is_shortcut {
+            continue;
+        }
+        if let (Some(path_str), Ok(metadata)) = (path.to_str(), path.metadata()) {
+            shortcuts.push((metadata.modified()?, path_str.to_string()));
+        }
+    }
+    shortcuts.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+
+    let _com = ComGuard::init()?;
+
+    let entries = shortcuts
+        .into_iter()
+        .filter_map(|(modified, lnk_path)| {
+            let target = resolve_shortcut_with_api(&lnk_path).ok()?;
+            (!target.is_empty()).then_some(ShortcutEntry {
+                lnk_path,
+                target,
+                modified,
+            })
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// Lists the targets of the `.lnk` shortcuts in a folder, most recently used first.
+pub(crate) fn list_shortcut_targets(folder: &Path) -> WincentResult<Vec<String>> {
+    Ok(list_shortcuts(folder)?
+        .into_iter()
+        .map(|entry| entry.target)
+        .collect())
+}
+
+/// Reads Quick Access items from the shortcut files on disk, as a fallback when the
+/// Shell namespace cannot be queried.
+///
+/// Recent files come from the Recent folder and frequent folders from the Links folder.
+/// Pins stored only in the jump list file cannot be recovered this way.
+pub(crate) fn query_recent_with_shortcuts(qa_type: QuickAccess) -> WincentResult<Vec<String>> {
+    let recent_files = || -> WincentResult<Vec<String>> {
+        let targets = list_shortcut_targets(Path::new(&get_windows_recent_folder()?))?;
+        Ok(targets
+            .into_iter()
+            .filter(|target| Path::new(target).is_file())
+            .collect())
+    };
+    let frequent_folders = || -> WincentResult<Vec<String>> {
+        let targets = list_shortcut_targets(Path::new(&get_known_folder_path(&FOLDERID_Links)?))?;
+        Ok(targets
+            .into_iter()
+            .filter(|target| Path::new(target).is_dir())
+            .collect())
+    };
+
+    match qa_type {
+        QuickAccess::RecentFiles => recent_files(),
+        QuickAccess::FrequentFolders => frequent_folders(),
+        QuickAccess::All => Ok([frequent_folders()?, recent_files()?].concat()),
+    }
+}
+
 /****************************************************** Query Quick Access ******************************************************/
 
 /// Gets a list of recent files from Windows Quick Access.
@@ -126,9 +378,7 @@ pub(crate) fn query_recent_with_ps_script(qa_type: QuickAccess) -> WincentResult
 /// ```
 pub fn get_recent_files() -> WincentResult<Vec<String>> {
     if !check_script_feasible()? {
-        return Err(WincentError::UnsupportedOperation(
-            "PowerShell script execution is not feasible".to_string(),
-        ));
+        return Err(WincentError::ExecutionPolicyRestricted);
     }
 
     if !check_query_feasible()? {
@@ -140,30 +390,39 @@ pub fn get_recent_files() -> WincentResult<Vec<String>> {
     query_recent_with_ps_script(QuickAccess::RecentFiles)
 }
 
-/// Gets a list of frequent folders from Windows Quick Access.
+/// Gets the `n` most recently used files from Windows Quick Access.
+///
+/// The Shell enumeration stops after `n` files, which is much faster than
+/// [`get_recent_files`] for large histories. Files are most recent first, in the
+/// order Explorer shows them.
+///
+/// # Arguments
+///
+/// * `n` - The maximum number of files to return
 ///
 /// # Returns
 ///
-/// Returns a vector of folder paths as strings.
+/// Returns at most `n` file paths as strings.
 ///
 /// # Example
 ///
-/// ```rust
-/// use wincent::{query::get_frequent_folders, error::WincentError};
+/// ```no_run
+/// use wincent::{query::get_recent_files_top, error::WincentError};
 ///
 /// fn main() -> Result<(), WincentError> {
-///     let folders = get_frequent_folders()?;
-///     for folder in folders {
-///         println!("Frequent folder: {}", folder);
+///     for file in get_recent_files_top(5)? {
+///         println!("Jump back in: {}", file);
 ///     }
 ///     Ok(())
 /// }
 /// ```
-pub fn get_frequent_folders() -> WincentResult<Vec<String>> {
+pub fn get_recent_files_top(n: usize) -> WincentResult<Vec<String>> {
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
     if !check_script_feasible()? {
-        return Err(WincentError::UnsupportedOperation(
-            "PowerShell script execution is not feasible".to_string(),
-        ));
+        return Err(WincentError::ExecutionPolicyRestricted);
     }
 
     if !check_query_feasible()? {
@@ -172,38 +431,104 @@ pub fn get_frequent_folders() -> WincentResult<Vec<String>> {
         ));
     }
 
-    query_recent_with_ps_script(QuickAccess::FrequentFolders)
+    let output = execute_ps_script(Script::QueryRecentFileTop, Some(&n.to_string()))?;
+
+    parse_output_to_paths(output)
 }
 
-/// Gets a list of all items from Windows Quick Access, including both recent files and frequent folders.
+/// Checks whether a Quick Access entry is a virtual shell item instead of a filesystem path.
+///
+/// Some applications, Office in particular, add recent entries such as `::{GUID}\...`
+/// which are not drive (`C:\...`) or UNC (`\\server\share`) paths.
+///
+/// # Arguments
+///
+/// * `path` - The entry as returned by a query
 ///
 /// # Returns
 ///
-/// Returns a vector of strings containing the paths of all Quick Access items.
+/// Returns `true` if the entry is not a drive or UNC path.
+///
+/// # Example
+///
+/// ```
+/// use wincent::query::is_virtual_path;
+///
+/// assert!(is_virtual_path("::{645FF040-5081-101B-9F08-00AA002F954E}"));
+/// assert!(!is_virtual_path("C:\\Documents\\report.docx"));
+/// ```
+pub fn is_virtual_path(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    let is_drive_path = bytes.len() >= 3
+        && bytes[0].is_ascii_alphabetic()
+        && bytes[1] == b':'
+        && (bytes[2] == b'\\' || bytes[2] == b'/');
+    let is_unc_path = path.starts_with("\\\\");
+
+    !(is_drive_path || is_unc_path)
+}
+
+/// Gets a list of recent files from Windows Quick Access, optionally without virtual entries.
+///
+/// With `include_virtual` set to `false`, entries that are not filesystem paths (see
+/// [`is_virtual_path`]) are left out, so the result can safely be used for path
+/// comparisons and validation.
+///
+/// # Arguments
+///
+/// * `include_virtual` - Whether virtual shell items are kept
+///
+/// # Returns
+///
+/// Returns a vector of recent file paths as strings.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_recent_files_filtered, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for file in get_recent_files_filtered(false)? {
+///         println!("Recent file on disk: {}", file);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_recent_files_filtered(include_virtual: bool) -> WincentResult<Vec<String>> {
+    let files = get_recent_files()?;
+
+    if include_virtual {
+        return Ok(files);
+    }
+
+    Ok(files
+        .into_iter()
+        .filter(|file| !is_virtual_path(file))
+        .collect())
+}
+
+/// Gets a list of frequent folders from Windows Quick Access.
+///
+/// # Returns
+///
+/// Returns a vector of folder paths as strings.
 ///
 /// # Example
 ///
 /// ```rust
-/// use wincent::{query::get_quick_access_items, error::WincentError};
+/// use wincent::{query::get_frequent_folders, error::WincentError};
 ///
 /// fn main() -> Result<(), WincentError> {
-///     match get_quick_access_items() {
-///         Ok(items) => {
-///             println!("Found {} Quick Access items:", items.len());
-///             for item in items {
-///                 println!("  - {}", item);
-///             }
-///         },
-///         Err(e) => println!("Failed to get Quick Access items: {}", e)
+///     let folders = get_frequent_folders()?;
+///     for folder in folders {
+///         println!("Frequent folder: {}", folder);
 ///     }
 ///     Ok(())
 /// }
 /// ```
-pub fn get_quick_access_items() -> WincentResult<Vec<String>> {
+pub fn get_frequent_folders() -> WincentResult<Vec<String>> {
     if !check_script_feasible()? {
-        return Err(WincentError::UnsupportedOperation(
-            "PowerShell script execution is not feasible".to_string(),
-        ));
+        return Err(WincentError::ExecutionPolicyRestricted);
     }
 
     if !check_query_feasible()? {
@@ -212,122 +537,1747 @@ pub fn get_quick_access_items() -> WincentResult<Vec<String>> {
         ));
     }
 
-    query_recent_with_ps_script(QuickAccess::All)
+    query_recent_with_ps_script(QuickAccess::FrequentFolders)
 }
 
-/****************************************************** Check Quick Access ******************************************************/
+/// Pairs items with their 1-based position in the list.
+pub(crate) fn rank_by_position(items: Vec<String>) -> Vec<(String, u32)> {
+    items.into_iter().zip(1..).collect()
+}
 
-/// Checks if a file path exists in the Windows Recent Files list.
-///
-/// # Arguments
+/// Gets the frequent folders along with their rank in Quick Access.
 ///
-/// * `keyword` - The file path or partial path to search for
+/// The rank is the 1-based position in which Explorer lists the folder: pinned folders
+/// first in pin order, then the other folders by Explorer's usage ranking. It is
+/// position-based, the raw usage counts are not exposed by the Shell.
 ///
 /// # Returns
 ///
-/// Returns `true` if the file is found in the recent files list.
+/// Returns the folder paths with their rank, best ranked first.
 ///
-/// # Example       
+/// # Example
 ///
-/// ```rust
-/// use wincent::{query::is_in_recent_files, error::WincentError};
+/// ```no_run
+/// use wincent::{query::get_frequent_folders_ranked, error::WincentError};
 ///
 /// fn main() -> Result<(), WincentError> {
-///     let file_exists = is_in_recent_files("Documents\\report.docx")?;
-///     if file_exists {
-///         println!("File found in recent files");
+///     for (folder, rank) in get_frequent_folders_ranked()? {
+///         println!("#{} {}", rank, folder);
 ///     }
 ///     Ok(())
 /// }
 /// ```
-pub fn is_in_recent_files(keyword: &str) -> WincentResult<bool> {
-    let items = get_recent_files()?;
-
-    Ok(items.iter().any(|item| item.contains(keyword)))
+pub fn get_frequent_folders_ranked() -> WincentResult<Vec<(String, u32)>> {
+    Ok(rank_by_position(get_frequent_folders()?))
 }
 
-/// Checks if a folder path exists in the Windows Frequent Folders list.
+/// Gets the shell verbs available on a file or folder.
+///
+/// Folders listed in Quick Access are looked up there, so Quick Access specific verbs
+/// such as `unpinfromhome` are included. The verbs are the canonical names passed to
+/// `InvokeVerb`, which do not depend on the Windows display language. Commands without
+/// a canonical name are left out.
 ///
 /// # Arguments
 ///
-/// * `keyword` - The folder path or partial path to search for
+/// * `path` - The full path to the file or folder
 ///
 /// # Returns
 ///
-/// Returns `true` if the folder is found in the frequent folders list.
+/// Returns the canonical names of the available verbs.
 ///
 /// # Example
-///     
-/// ```rust
-/// use wincent::{query::is_in_frequent_folders, error::WincentError};
+///
+/// ```no_run
+/// use wincent::{query::available_verbs, error::WincentError};
 ///
 /// fn main() -> Result<(), WincentError> {
-///     let folder_exists = is_in_frequent_folders("Projects")?;
-///     if folder_exists {
-///         println!("Found folder in frequent folders list");
-///     } else {
-///         println!("Folder not found in frequent folders list");
+///     if available_verbs("C:\\Projects")?.iter().any(|verb| verb == "unpinfromhome") {
+///         println!("C:\\Projects can be unpinned");
 ///     }
 ///     Ok(())
 /// }
 /// ```
-pub fn is_in_frequent_folders(keyword: &str) -> WincentResult<bool> {
-    let items = get_frequent_folders()?;
+pub fn available_verbs(path: &str) -> WincentResult<Vec<String>> {
+    validate_entry_path(path)?;
 
-    Ok(items.iter().any(|item| item.contains(keyword)))
+    let _com = ComGuard::init()?;
+
+    let item = match find_frequent_folder_item(path)? {
+        Some(item) => item,
+        None => unsafe {
+            SHCreateItemFromParsingName(&HSTRING::from(normalize_path(path)), None::<&IBindCtx>)
+        }?,
+    };
+
+    get_item_verbs(&item)
 }
 
-/// Checks if a path exists in the Windows Quick Access list.
+/// Gets the verbs of a folder listed in the frequent folders namespace.
 ///
-/// # Arguments
+/// Returns `None` if the folder is not listed in Quick Access.
+pub(crate) fn get_frequent_folder_verbs(path: &str) -> WincentResult<Option<Vec<String>>> {
+    let _com = ComGuard::init()?;
+
+    find_frequent_folder_item(path)?
+        .map(|item| get_item_verbs(&item))
+        .transpose()
+}
+
+/// Looks up a folder among the items of the frequent folders namespace.
+fn find_frequent_folder_item(path: &str) -> WincentResult<Option<IShellItem>> {
+    let namespace: IShellItem = unsafe {
+        SHCreateItemFromParsingName(
+            &HSTRING::from(FREQUENT_FOLDERS_NAMESPACE),
+            None::<&IBindCtx>,
+        )
+    }?;
+    let items: IEnumShellItems =
+        unsafe { namespace.BindToHandler(None::<&IBindCtx>, &BHID_EnumItems) }?;
+
+    loop {
+        let mut batch = [None];
+        let mut fetched = 0;
+        unsafe { items.Next(&mut batch, Some(&mut fetched)) }?;
+        let Some(item) = batch[0].take().filter(|_| fetched == 1) else {
+            return Ok(None);
+        };
+
+        let name = unsafe { item.GetDisplayName(SIGDN_DESKTOPABSOLUTEPARSING) }?;
+        let listed = unsafe {
+            let listed = String::from_utf16_lossy(name.as_wide());
+            CoTaskMemFree(Some(name.as_ptr() as _));
+            listed
+        };
+        if paths_equal(&listed, path) {
+            return Ok(Some(item));
+        }
+    }
+}
+
+/// Destroys a popup menu when dropped.
+struct MenuGuard(HMENU);
+
+impl Drop for MenuGuard {
+    fn drop(&mut self) {
+        let _ = unsafe { DestroyMenu(self.0) };
+    }
+}
+
+/// Gets the canonical verb names of the context menu commands of a shell item.
+fn get_item_verbs(item: &IShellItem) -> WincentResult<Vec<String>> {
+    let context_menu: IContextMenu =
+        unsafe { item.BindToHandler(None::<&IBindCtx>, &BHID_SFUIObject) }?;
+    let menu = MenuGuard(unsafe { CreatePopupMenu() }?);
+    unsafe {
+        context_menu.QueryContextMenu(menu.0, 0, FIRST_MENU_COMMAND, LAST_MENU_COMMAND, CMF_NORMAL)
+    }?;
+
+    let mut verbs = Vec::new();
+    for position in 0..unsafe { GetMenuItemCount(menu.0) } {
+        // Separators and submenus have no command identifier
+        let id = unsafe { GetMenuItemID(menu.0, position) };
+        if !(FIRST_MENU_COMMAND..=LAST_MENU_COMMAND).contains(&id) {
+            continue;
+        }
+
+        let mut buffer = [0u16; 256];
+        let named = unsafe {
+            context_menu.GetCommandString(
+                (id - FIRST_MENU_COMMAND) as usize,
+                GCS_VERBW,
+                None,
+                PSTR(buffer.as_mut_ptr().cast()),
+                buffer.len() as u32,
+            )
+        };
+        let length = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        if named.is_ok() && length > 0 {
+            verbs.push(String::from_utf16_lossy(&buffer[..length]));
+        }
+    }
+
+    Ok(verbs)
+}
+
+/// Gets a list of the folders pinned to Windows Quick Access.
 ///
-/// * `keyword` - The path or partial path to search for
+/// Unlike [`get_frequent_folders`], folders added automatically because they are
+/// often visited are left out.
 ///
 /// # Returns
 ///
-/// Returns `true` if the path is found in either recent files or frequent folders.
+/// Returns a vector of pinned folder paths as strings.
 ///
 /// # Example
-///     
-/// ```rust
-/// use wincent::{query::is_in_quick_access, error::WincentError};
 ///
-/// fn main() -> Result<(), WincentError> {
-///     // Check for a specific file or folder
-///     if is_in_quick_access("Documents")? {
-///         println!("Found item in Quick Access");
-///     }
+/// ```no_run
+/// use wincent::{query::get_pinned_folders, error::WincentError};
 ///
-///     // Check for items in a specific location
-///     if is_in_quick_access("C:\\Projects\\")? {
-///         println!("Found items from Projects folder");
+/// fn main() -> Result<(), WincentError> {
+///     for folder in get_pinned_folders()? {
+///         println!("Pinned folder: {}", folder);
 ///     }
 ///     Ok(())
 /// }
 /// ```
-pub fn is_in_quick_access(keyword: &str) -> WincentResult<bool> {
-    let items = get_quick_access_items()?;
+pub fn get_pinned_folders() -> WincentResult<Vec<String>> {
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
 
-    Ok(items.iter().any(|item| item.contains(keyword)))
-}
+    if !check_query_feasible()? {
+        return Err(WincentError::UnsupportedOperation(
+            "Quick Access query operation is not feasible".to_string(),
+        ));
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    query_pinned_with_ps_script()
+}
 
-    #[test]
-    fn test_query_recent_files() -> WincentResult<()> {
-        let files = query_recent_with_ps_script(QuickAccess::RecentFiles)?;
+/// Lists pinned folders first in pin order, followed by the remaining frequent folders.
+pub(crate) fn order_pinned_first(pinned: Vec<String>, frequent: Vec<String>) -> Vec<FolderEntry> {
+    let unpinned: Vec<String> = frequent
+        .into_iter()
+        .filter(|folder| !pinned.iter().any(|p| paths_equal(p, folder)))
+        .collect();
 
-        if !files.is_empty() {
-            assert!(
-                files.iter().all(|path| !path.is_empty()),
-                "Paths should not be empty"
-            );
+    pinned
+        .into_iter()
+        .map(|path| FolderEntry { path, pinned: true })
+        .chain(unpinned.into_iter().map(|path| FolderEntry {
+            path,
+            pinned: false,
+        }))
+        .collect()
+}
 
-            for path in &files {
-                assert!(
-                    path.contains(":\\"),
+/// Gets the frequent folders grouped the way Explorer shows them.
+///
+/// Pinned folders come first in pin order, followed by the folders listed because they
+/// are often visited, in Explorer's usage order.
+///
+/// # Returns
+///
+/// Returns the folders, each tagged with whether it is pinned.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_frequent_folders_ordered, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for folder in get_frequent_folders_ordered()? {
+///         let marker = if folder.pinned { "*" } else { " " };
+///         println!("{} {}", marker, folder.path);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_frequent_folders_ordered() -> WincentResult<Vec<FolderEntry>> {
+    let pinned = get_pinned_folders()?;
+    let frequent = query_recent_with_ps_script(QuickAccess::FrequentFolders)?;
+
+    Ok(order_pinned_first(pinned, frequent))
+}
+
+/// Gets the frequent folders listed because they are often visited, leaving out pinned ones.
+///
+/// These are the folders the user works in without having pinned them, good candidates
+/// to suggest pinning.
+///
+/// # Returns
+///
+/// Returns the unpinned frequent folders in Explorer's usage order.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_unpinned_frequent_folders, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for folder in get_unpinned_frequent_folders()? {
+///         println!("Consider pinning {}", folder);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_unpinned_frequent_folders() -> WincentResult<Vec<String>> {
+    Ok(unpinned_only(get_frequent_folders_ordered()?))
+}
+
+/// Keeps the paths of the folders that are not pinned.
+pub(crate) fn unpinned_only(folders: Vec<FolderEntry>) -> Vec<String> {
+    folders
+        .into_iter()
+        .filter(|folder| !folder.pinned)
+        .map(|folder| folder.path)
+        .collect()
+}
+
+/// Gets a list of all items from Windows Quick Access, including both recent files and frequent folders.
+///
+/// # Returns
+///
+/// Returns a vector of strings containing the paths of all Quick Access items.
+///
+/// # Example
+///
+/// ```rust
+/// use wincent::{query::get_quick_access_items, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     match get_quick_access_items() {
+///         Ok(items) => {
+///             println!("Found {} Quick Access items:", items.len());
+///             for item in items {
+///                 println!("  - {}", item);
+///             }
+///         },
+///         Err(e) => println!("Failed to get Quick Access items: {}", e)
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_quick_access_items() -> WincentResult<Vec<String>> {
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    if !check_query_feasible()? {
+        return Err(WincentError::UnsupportedOperation(
+            "Quick Access query operation is not feasible".to_string(),
+        ));
+    }
+
+    query_recent_with_ps_script(QuickAccess::All)
+}
+
+/// Bucket of [`group_by_volume`] holding entries that are not filesystem paths.
+pub const OTHER_VOLUME: &str = "other";
+
+/// Gets the volume an entry is stored on: `C:` for drive paths, `\\server\share` for
+/// UNC paths, [`OTHER_VOLUME`] for virtual shell items.
+pub(crate) fn volume_of(path: &str) -> String {
+    let path = normalize_path(path);
+
+    if is_virtual_path(&path) {
+        return OTHER_VOLUME.to_string();
+    }
+
+    if let Some(unc) = path.strip_prefix("\\\\") {
+        let mut parts = unc.split('\\').filter(|part| !part.is_empty());
+        return match (parts.next(), parts.next()) {
+            (Some(server), Some(share)) => format!("\\\\{}\\{}", server, share),
+            _ => OTHER_VOLUME.to_string(),
+        };
+    }
+
+    path[..2].to_uppercase()
+}
+
+/// Buckets entries by their volume, keeping the Quick Access order within each bucket.
+pub(crate) fn group_items_by_volume(items: Vec<String>) -> HashMap<String, Vec<String>> {
+    let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+
+    for item in items {
+        groups.entry(volume_of(&item)).or_default().push(item);
+    }
+
+    groups
+}
+
+/// Gets all Quick Access items grouped by the volume they are stored on.
+///
+/// Drive paths are grouped by drive (`C:`), UNC paths by share (`\\server\share`).
+/// Virtual shell items that are not filesystem paths go to the [`OTHER_VOLUME`] bucket.
+///
+/// # Returns
+///
+/// Returns the items per volume, in Quick Access order within each volume.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::group_by_volume, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for (volume, items) in group_by_volume()? {
+///         println!("{}: {} items", volume, items.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn group_by_volume() -> WincentResult<HashMap<String, Vec<String>>> {
+    Ok(group_items_by_volume(get_quick_access_items()?))
+}
+
+/// Most threads probing entries at the same time.
+const MAX_PROBE_WORKERS: usize = 8;
+
+/// Probes the entries on a few threads and splits them by the result.
+///
+/// Entries are grouped by volume or network share, and each group is probed in turn by
+/// one of at most [`MAX_PROBE_WORKERS`] threads, so a dead share ties up a single thread
+/// however many of its entries are listed. Probes still running when the timeout expires
+/// are left to finish in the background, and their entries as well as the ones not
+/// probed yet are counted as unreachable. Virtual shell items are not probed and always
+/// count as reachable.
+pub(crate) fn probe_reachability<F>(items: Vec<String>, timeout: Duration, probe: F) -> Reachability
+where
+    F: Fn(&str) -> bool + Send + Sync + 'static,
+{
+    let probe = Arc::new(probe);
+    let (sender, receiver) = mpsc::channel();
+    let mut results = vec![false; items.len()];
+
+    let mut volumes: HashMap<String, Vec<(usize, String)>> = HashMap::new();
+    for (index, item) in items.iter().enumerate() {
+        if is_virtual_path(item) {
+            results[index] = true;
+            continue;
+        }
+        volumes
+            .entry(volume_of(item))
+            .or_default()
+            .push((index, item.clone()));
+    }
+
+    let workers = volumes.len().min(MAX_PROBE_WORKERS);
+    let queue = Arc::new(Mutex::new(volumes.into_values().collect::<Vec<_>>()));
+    for _ in 0..workers {
+        let probe = Arc::clone(&probe);
+        let sender = sender.clone();
+        let queue = Arc::clone(&queue);
+        std::thread::spawn(move || loop {
+            let next = queue.lock().unwrap_or_else(|e| e.into_inner()).pop();
+            let Some(entries) = next else {
+                break;
+            };
+            for (index, path) in entries {
+                // Nobody is waiting for the results anymore once the timeout expired
+                if sender.send((index, probe(&path))).is_err() {
+                    return;
+                }
+            }
+        });
+    }
+    drop(sender);
+
+    let deadline = Instant::now() + timeout;
+    while let Ok((index, reachable)) =
+        receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+    {
+        results[index] = reachable;
+    }
+
+    let mut report = Reachability::default();
+    for (item, reachable) in items.into_iter().zip(results) {
+        if reachable {
+            report.reachable.push(item);
+        } else {
+            report.unreachable.push(item);
+        }
+    }
+
+    report
+}
+
+/// Gets all Quick Access items, skipping those whose paths cannot be reached in time.
+///
+/// Checking a path on a disconnected network share can block for a long time. Paths are
+/// checked on a few threads, one volume or share at a time per thread, so a dead share
+/// only costs `probe_timeout` instead of stalling the whole listing. Virtual shell items
+/// are always reported as reachable.
+///
+/// # Arguments
+///
+/// * `probe_timeout` - How long to wait for all existence checks in total
+///
+/// # Returns
+///
+/// Returns the reachable items and, separately, the missing or unresponsive ones.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wincent::{query::get_quick_access_items_reachable, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let report = get_quick_access_items_reachable(Duration::from_secs(2))?;
+///     println!("{} reachable items", report.reachable.len());
+///     for item in report.unreachable {
+///         println!("Unreachable: {}", item);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_quick_access_items_reachable(probe_timeout: Duration) -> WincentResult<Reachability> {
+    Ok(probe_reachability(
+        get_quick_access_items()?,
+        probe_timeout,
+        |path| Path::new(path).exists(),
+    ))
+}
+
+/// Gets the last modification time of the data files backing a Quick Access category.
+///
+/// This reads file metadata only, so it is much cheaper than running a query and can be
+/// used to decide whether the items need to be queried again.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category, `QuickAccess::All` returns the latest of both
+///
+/// # Returns
+///
+/// Returns the modification time, or `SystemTime::UNIX_EPOCH` if the data file does not
+/// exist yet or is truncated (for example right after the category was cleared).
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_last_modified, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let before = get_last_modified(QuickAccess::RecentFiles)?;
+///     // ... later
+///     if get_last_modified(QuickAccess::RecentFiles)? != before {
+///         println!("Recent files changed");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_last_modified(qa_type: QuickAccess) -> WincentResult<SystemTime> {
+    let mut latest = SystemTime::UNIX_EPOCH;
+
+    for data_file in get_quick_access_data_files(qa_type)? {
+        if let Some(modified) = get_jumplist_modified(&data_file)? {
+            latest = latest.max(modified);
+        }
+    }
+
+    Ok(latest)
+}
+
+/// Picks the most recently used shortcut whose target passes `exists`.
+pub(crate) fn latest_existing<F>(
+    shortcuts: Vec<ShortcutEntry>,
+    exists: F,
+) -> Option<(String, SystemTime)>
+where
+    F: Fn(&str) -> bool,
+{
+    shortcuts
+        .into_iter()
+        .filter(|shortcut| exists(&shortcut.target))
+        .max_by_key(|shortcut| shortcut.modified)
+        .map(|shortcut| (shortcut.target, shortcut.modified))
+}
+
+/// Gets the most recently used item, whether a file or a folder.
+///
+/// Windows keeps a shortcut in the Recent folder for every file and folder opened, and
+/// touches it on each access. The target of the newest shortcut that still exists is the
+/// item last used, without querying the whole Quick Access lists.
+///
+/// # Returns
+///
+/// Returns the item path with the time it was last used, or `None` if there are no
+/// recent items.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::most_recent, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     if let Some((path, used)) = most_recent()? {
+///         println!("Last used {} at {:?}", path, used);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn most_recent() -> WincentResult<Option<(String, SystemTime)>> {
+    let recent_folder = get_windows_recent_folder()?;
+    let shortcuts = list_shortcuts(Path::new(&recent_folder))?;
+
+    Ok(latest_existing(shortcuts, |target| {
+        Path::new(target).exists()
+    }))
+}
+
+/// Keeps the items whose newest matching shortcut was touched between `start` and `end`.
+///
+/// Items without a shortcut have no known access time and are left out.
+pub(crate) fn used_between(
+    items: Vec<String>,
+    shortcuts: &[ShortcutEntry],
+    start: SystemTime,
+    end: SystemTime,
+) -> Vec<String> {
+    items
+        .into_iter()
+        .filter(|item| {
+            shortcuts
+                .iter()
+                .filter(|shortcut| paths_equal(&shortcut.target, item))
+                .map(|shortcut| shortcut.modified)
+                .max()
+                .is_some_and(|used| start <= used && used <= end)
+        })
+        .collect()
+}
+
+/// Gets the recent files last used within a time range.
+///
+/// The time a file was last used is taken from its shortcut in the Recent folder, which
+/// Windows touches on each access. Recent files without a shortcut have no known access
+/// time and are skipped.
+///
+/// # Arguments
+///
+/// * `start` - Start of the range, inclusive
+/// * `end` - End of the range, inclusive
+///
+/// # Returns
+///
+/// Returns the matching recent files in Quick Access order. Fails with
+/// [`WincentError::InvalidArgument`] if `start` is after `end`.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::{Duration, SystemTime};
+/// use wincent::{query::recent_files_between, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let now = SystemTime::now();
+///     let last_week = now - Duration::from_secs(7 * 24 * 60 * 60);
+///     for file in recent_files_between(last_week, now)? {
+///         println!("{}", file);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn recent_files_between(start: SystemTime, end: SystemTime) -> WincentResult<Vec<String>> {
+    if start > end {
+        return Err(WincentError::InvalidArgument(
+            "Start of the time range is after its end".to_string(),
+        ));
+    }
+
+    let recent_folder = get_windows_recent_folder()?;
+    let shortcuts = list_shortcuts(Path::new(&recent_folder))?;
+
+    Ok(used_between(get_recent_files()?, &shortcuts, start, end))
+}
+
+/// Registry key under `HKEY_CURRENT_USER` holding the paths typed into the Explorer
+/// address bar, as `url1` (most recent) to `urlN` string values.
+pub(crate) const TYPED_PATHS_KEY: &str =
+    "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\TypedPaths";
+
+/// Orders the `urlN` values of the typed paths key by their number, ignoring other values.
+pub(crate) fn order_typed_paths(values: Vec<(String, String)>) -> Vec<String> {
+    let mut numbered: Vec<(u32, String)> = values
+        .into_iter()
+        .filter_map(|(name, path)| {
+            let index = name
+                .get(..3)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("url"))
+                .and_then(|_| name[3..].parse().ok())?;
+            Some((index, path))
+        })
+        .collect();
+    numbered.sort_by_key(|(index, _)| *index);
+
+    numbered.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Gets the paths typed into the Explorer address bar, most recent first.
+///
+/// Explorer keeps them apart from Quick Access, in the `url1` to `urlN` values of
+/// `HKEY_CURRENT_USER\Software\Microsoft\Windows\CurrentVersion\Explorer\TypedPaths`.
+///
+/// # Returns
+///
+/// Returns the typed paths, empty if the key does not exist.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_address_bar_mru, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for path in get_address_bar_mru()? {
+///         println!("Typed: {}", path);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_address_bar_mru() -> WincentResult<Vec<String>> {
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_READ};
+    use winreg::RegKey;
+
+    let key =
+        match RegKey::predef(HKEY_CURRENT_USER).open_subkey_with_flags(TYPED_PATHS_KEY, KEY_READ) {
+            Ok(key) => key,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(WincentError::Io(e)),
+        };
+
+    let mut values = Vec::new();
+    for value in key.enum_values() {
+        let (name, _) = value?;
+        // Values that are not strings are not paths Explorer wrote
+        if let Ok(path) = key.get_value::<String, _>(&name) {
+            values.push((name, path));
+        }
+    }
+
+    Ok(order_typed_paths(values))
+}
+
+/// Delay between two queries of [`wait_for`].
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Runs `check` until it returns `true` or `timeout` elapses, sleeping `interval` between
+/// runs. `check` runs at least once.
+pub(crate) fn poll_until<F>(
+    timeout: Duration,
+    interval: Duration,
+    mut check: F,
+) -> WincentResult<bool>
+where
+    F: FnMut() -> WincentResult<bool>,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        if check()? {
+            return Ok(true);
+        }
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(false);
+        }
+        std::thread::sleep(interval.min(remaining));
+    }
+}
+
+/// Waits until an item appears in or disappears from Quick Access.
+///
+/// Explorer updates Quick Access asynchronously, so an item added or removed may take a
+/// moment to be listed accordingly. Quick Access is queried again every 200 milliseconds
+/// until the item is in the requested state or the timeout elapses.
+///
+/// # Arguments
+///
+/// * `path` - The full path of the item
+/// * `qa_type` - The Quick Access category to look in
+/// * `present` - `true` to wait until the item is listed, `false` until it is gone
+/// * `timeout` - How long to keep waiting
+///
+/// # Returns
+///
+/// Returns `true` if the item reached the requested state in time.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wincent::{
+///     handle::add_to_frequent_folders, query::wait_for, QuickAccess, error::WincentError,
+/// };
+///
+/// fn main() -> Result<(), WincentError> {
+///     add_to_frequent_folders("C:\\Projects")?;
+///     if !wait_for("C:\\Projects", QuickAccess::FrequentFolders, true, Duration::from_secs(5))? {
+///         println!("Explorer has not listed the folder yet");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn wait_for(
+    path: &str,
+    qa_type: QuickAccess,
+    present: bool,
+    timeout: Duration,
+) -> WincentResult<bool> {
+    poll_until(timeout, WAIT_POLL_INTERVAL, || {
+        let items = query_recent_with_ps_script(qa_type)?;
+        Ok(items.iter().any(|item| paths_equal(item, path)) == present)
+    })
+}
+
+/// Gets the items of a Quick Access category with the matching public query.
+pub(crate) fn get_items_by_type(qa_type: QuickAccess) -> WincentResult<Vec<String>> {
+    match qa_type {
+        QuickAccess::RecentFiles => get_recent_files(),
+        QuickAccess::FrequentFolders => get_frequent_folders(),
+        QuickAccess::All => get_quick_access_items(),
+    }
+}
+
+/// Pairs each item with its normalized form.
+pub(crate) fn with_normalized(items: Vec<String>) -> Vec<(String, String)> {
+    items
+        .into_iter()
+        .map(|item| {
+            let normalized = normalize_path(&item);
+            (item, normalized)
+        })
+        .collect()
+}
+
+/// Gets the Quick Access items of a category as reported by Explorer and normalized.
+///
+/// The normalized form has trailing separators removed, except for drive roots, and
+/// is what the crate compares paths with. Keep the raw form for display.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to query
+///
+/// # Returns
+///
+/// Returns pairs of the raw path and its normalized form.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_items_normalized, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for (raw, normalized) in get_items_normalized(QuickAccess::FrequentFolders)? {
+///         println!("{} ({})", raw, normalized);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_items_normalized(qa_type: QuickAccess) -> WincentResult<Vec<(String, String)>> {
+    Ok(with_normalized(get_items_by_type(qa_type)?))
+}
+
+/// Gets the Quick Access items of a category, giving up after a timeout.
+///
+/// Useful to bound how long a UI waits, while background work can allow more time.
+/// The timeout applies to the query itself, not to the feasibility checks before it.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to query
+/// * `timeout` - How long the query may run before it is stopped
+///
+/// # Returns
+///
+/// Returns the item paths, or [`WincentError::Timeout`] if the query took too long.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use wincent::{query::get_items_timeout, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     match get_items_timeout(QuickAccess::RecentFiles, Duration::from_secs(2)) {
+///         Ok(items) => println!("{} recent files", items.len()),
+///         Err(WincentError::Timeout(_)) => println!("Explorer is busy, try again later"),
+///         Err(e) => return Err(e),
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_items_timeout(qa_type: QuickAccess, timeout: Duration) -> WincentResult<Vec<String>> {
+    if !check_script_feasible()? {
+        return Err(WincentError::ExecutionPolicyRestricted);
+    }
+
+    if !check_query_feasible()? {
+        return Err(WincentError::UnsupportedOperation(
+            "Quick Access query operation is not feasible".to_string(),
+        ));
+    }
+
+    query_recent_with_timeout(qa_type, timeout)
+}
+
+/// Gets the Quick Access items of a category accepted by a predicate.
+///
+/// The category is queried once and the predicate is called with each item path.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to query
+/// * `predicate` - Returns `true` for the paths to keep
+///
+/// # Returns
+///
+/// Returns the matching item paths, in Quick Access order.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::query_with_filter, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let reports = query_with_filter(QuickAccess::RecentFiles, |path| {
+///         path.to_lowercase().ends_with(".docx")
+///     })?;
+///     println!("Found {} recent reports", reports.len());
+///     Ok(())
+/// }
+/// ```
+pub fn query_with_filter(
+    qa_type: QuickAccess,
+    predicate: impl Fn(&str) -> bool,
+) -> WincentResult<Vec<String>> {
+    let items = get_items_by_type(qa_type)?;
+
+    Ok(items.into_iter().filter(|item| predicate(item)).collect())
+}
+
+/// Checks whether an item path contains a keyword, ignoring case.
+pub(crate) fn contains_keyword(item: &str, keyword: &str) -> bool {
+    item.to_lowercase().contains(&keyword.to_lowercase())
+}
+
+/// Gets the Quick Access items of a category whose path contains a keyword.
+///
+/// The keyword is matched anywhere in the path and case is ignored, so `"project"`
+/// matches both `C:\Projects\app` and `D:\old-project.txt`. Use it to preview what
+/// [`crate::handle::find_and_remove`] would remove.
+///
+/// # Arguments
+///
+/// * `keyword` - The part of the path to look for, must not be empty
+/// * `qa_type` - The Quick Access category to search
+///
+/// # Returns
+///
+/// Returns the matching item paths, in Quick Access order.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::find_matching_items, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for item in find_matching_items("old-project", QuickAccess::All)? {
+///         println!("Would remove: {}", item);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn find_matching_items(keyword: &str, qa_type: QuickAccess) -> WincentResult<Vec<String>> {
+    if keyword.is_empty() {
+        return Err(WincentError::InvalidArgument(
+            "Empty keyword would match every item".to_string(),
+        ));
+    }
+
+    query_with_filter(qa_type, |item| contains_keyword(item, keyword))
+}
+
+/// Gets Quick Access items, falling back to reading shortcut files if the query fails.
+///
+/// When the Shell namespace query fails (for example on a broken Shell configuration),
+/// recent files are read from the shortcuts in the Recent folder and frequent folders
+/// from the shortcuts in the Links folder. Such results are marked as `degraded` since
+/// they may be incomplete, pins in particular are usually missing.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to query
+///
+/// # Returns
+///
+/// Returns the items and whether they come from the fallback. If the fallback fails too,
+/// the error of the original query is returned.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_quick_access_items_with_fallback, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let outcome = get_quick_access_items_with_fallback(QuickAccess::RecentFiles)?;
+///     if outcome.degraded {
+///         println!("Shell query failed, showing best-effort results");
+///     }
+///     for item in outcome.items {
+///         println!("{}", item);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_quick_access_items_with_fallback(qa_type: QuickAccess) -> WincentResult<QueryOutcome> {
+    let primary = get_items_by_type(qa_type);
+
+    match primary {
+        Ok(items) => Ok(QueryOutcome {
+            items,
+            degraded: false,
+        }),
+        Err(err) => match query_recent_with_shortcuts(qa_type) {
+            Ok(items) => Ok(QueryOutcome {
+                items,
+                degraded: true,
+            }),
+            Err(_) => Err(err),
+        },
+    }
+}
+
+/// Takes a window of at most `limit` items starting at `offset`, along with the total count.
+pub(crate) fn paginate(items: Vec<String>, offset: usize, limit: usize) -> (Vec<String>, usize) {
+    let total = items.len();
+    let page = items.into_iter().skip(offset).take(limit).collect();
+
+    (page, total)
+}
+
+/// Gets a page of Quick Access items, for rendering large lists incrementally.
+///
+/// The whole category is still queried once per call, only the returned window is
+/// limited. An `offset` past the end gives an empty page rather than an error.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to query
+/// * `offset` - Index of the first item of the page
+/// * `limit` - Maximum number of items in the page
+///
+/// # Returns
+///
+/// Returns the items of the page and the total number of items in the category.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::get_items_paged, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let (page, total) = get_items_paged(QuickAccess::RecentFiles, 0, 20)?;
+///     println!("Showing {} of {} recent files", page.len(), total);
+///     Ok(())
+/// }
+/// ```
+pub fn get_items_paged(
+    qa_type: QuickAccess,
+    offset: usize,
+    limit: usize,
+) -> WincentResult<(Vec<String>, usize)> {
+    let items = get_items_by_type(qa_type)?;
+
+    Ok(paginate(items, offset, limit))
+}
+
+/// Checks whether a Quick Access category contains any item.
+///
+/// An empty Quick Access is not an error, queries then return an empty vector.
+///
+/// # Arguments
+///
+/// * `qa_type` - The Quick Access category to check
+///
+/// # Returns
+///
+/// Returns `true` if the category has at least one item.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::has_items, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     if !has_items(QuickAccess::RecentFiles)? {
+///         println!("No recent files");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn has_items(qa_type: QuickAccess) -> WincentResult<bool> {
+    let items = get_items_by_type(qa_type)?;
+
+    Ok(!items.is_empty())
+}
+
+/// Checks whether the frequent folders jump list file is a valid OLE compound document.
+///
+/// A corrupted jump list makes frequent folder queries return errors or garbage, use
+/// [`crate::empty::repair_jumplist`] to recover from it.
+///
+/// # Returns
+///
+/// Returns the health of the jump list file.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{
+///     empty::repair_jumplist,
+///     query::{validate_jumplist, JumplistHealth},
+///     error::WincentError,
+/// };
+///
+/// fn main() -> Result<(), WincentError> {
+///     if validate_jumplist()? == JumplistHealth::Corrupt {
+///         repair_jumplist()?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn validate_jumplist() -> WincentResult<JumplistHealth> {
+    for data_file in get_quick_access_data_files(QuickAccess::FrequentFolders)? {
+        let health = get_jumplist_health(&data_file)?;
+        if health != JumplistHealth::Valid {
+            return Ok(health);
+        }
+    }
+
+    Ok(JumplistHealth::Valid)
+}
+
+/// Checks the health of a single jump list data file.
+///
+/// Only the size and the compound document header are read, however large the file is.
+pub(crate) fn get_jumplist_health(path: &Path) -> WincentResult<JumplistHealth> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(JumplistHealth::Missing),
+        Err(e) => return Err(WincentError::Io(e)),
+    };
+
+    // Empty and truncated files hold no data either, Windows refills them
+    let size = file.metadata()?.len();
+    if size < COMPOUND_HEADER_SIZE as u64 {
+        return Ok(JumplistHealth::Missing);
+    }
+    if size > MAX_JUMPLIST_SIZE {
+        return Ok(JumplistHealth::Corrupt);
+    }
+
+    let mut header = Vec::with_capacity(COMPOUND_HEADER_SIZE);
+    file.take(COMPOUND_HEADER_SIZE as u64)
+        .read_to_end(&mut header)?;
+
+    if is_compound_header(&header) {
+        Ok(JumplistHealth::Valid)
+    } else {
+        Ok(JumplistHealth::Corrupt)
+    }
+}
+
+/****************************************************** Check Quick Access ******************************************************/
+
+/// Checks if a file path exists in the Windows Recent Files list.
+///
+/// # Arguments
+///
+/// * `keyword` - The file path or partial path to search for
+///
+/// # Returns
+///
+/// Returns `true` if the file is found in the recent files list.
+///
+/// # Example       
+///
+/// ```rust
+/// use wincent::{query::is_in_recent_files, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let file_exists = is_in_recent_files("Documents\\report.docx")?;
+///     if file_exists {
+///         println!("File found in recent files");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn is_in_recent_files(keyword: &str) -> WincentResult<bool> {
+    let items = get_recent_files()?;
+
+    Ok(items.iter().any(|item| item.contains(keyword)))
+}
+
+/// Resolves a `.lnk` file to its target, other paths are returned unchanged.
+pub(crate) fn resolve_if_shortcut(path: &str) -> WincentResult<String> {
+    let path_buf = Path::new(path);
+    let is_shortcut = path_buf
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("lnk"));
+
+    if !is_shortcut || !path_buf.is_file() {
+        return Ok(path.to_string());
+    }
+
+    let target = resolve_shortcut(path)?;
+    Ok(if target.is_empty() {
+        path.to_string()
+    } else {
+        target
+    })
+}
+
+/// Checks if any shortcut points to a target.
+pub(crate) fn contains_target(shortcuts: &[ShortcutEntry], target: &str) -> bool {
+    shortcuts
+        .iter()
+        .any(|shortcut| paths_equal(&shortcut.target, target))
+}
+
+/// Checks if a file is in the Windows recent items, comparing shortcut targets.
+///
+/// Windows keeps one shortcut per target in the Recent folder, whatever its name, so
+/// two differently named shortcuts can stand for the same file. Here `path` may be the
+/// file itself or any shortcut to it, and it is found if the recent files list or any
+/// shortcut in the Recent folder points to the same target. Unlike
+/// [`is_in_recent_files`], the whole path has to match.
+///
+/// # Arguments
+///
+/// * `path` - The full path to the file, or to a `.lnk` shortcut to it
+///
+/// # Returns
+///
+/// Returns `true` if the target is already recorded as a recent item.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{
+///     handle::add_to_recent_files,
+///     query::is_in_recent_files_by_target,
+///     error::WincentError,
+/// };
+///
+/// fn main() -> Result<(), WincentError> {
+///     let path = "C:\\Documents\\report.docx";
+///     if !is_in_recent_files_by_target(path)? {
+///         add_to_recent_files(path)?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn is_in_recent_files_by_target(path: &str) -> WincentResult<bool> {
+    if path.is_empty() {
+        return Err(WincentError::InvalidPath("Empty path provided".to_string()));
+    }
+
+    let target = resolve_if_shortcut(path)?;
+
+    let recent_files = get_recent_files()?;
+    if recent_files.iter().any(|item| paths_equal(item, &target)) {
+        return Ok(true);
+    }
+
+    let recent_folder = get_windows_recent_folder()?;
+    let shortcuts = list_shortcuts(Path::new(&recent_folder))?;
+
+    Ok(contains_target(&shortcuts, &target))
+}
+
+/// Checks if a folder path exists in the Windows Frequent Folders list.
+///
+/// # Arguments
+///
+/// * `keyword` - The folder path or partial path to search for
+///
+/// # Returns
+///
+/// Returns `true` if the folder is found in the frequent folders list.
+///
+/// # Example
+///     
+/// ```rust
+/// use wincent::{query::is_in_frequent_folders, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let folder_exists = is_in_frequent_folders("Projects")?;
+///     if folder_exists {
+///         println!("Found folder in frequent folders list");
+///     } else {
+///         println!("Folder not found in frequent folders list");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn is_in_frequent_folders(keyword: &str) -> WincentResult<bool> {
+    let items = get_frequent_folders()?;
+
+    Ok(items.iter().any(|item| item.contains(keyword)))
+}
+
+/// Checks if a path exists in the Windows Quick Access list.
+///
+/// # Arguments
+///
+/// * `keyword` - The path or partial path to search for
+///
+/// # Returns
+///
+/// Returns `true` if the path is found in either recent files or frequent folders.
+///
+/// # Example
+///     
+/// ```rust
+/// use wincent::{query::is_in_quick_access, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     // Check for a specific file or folder
+///     if is_in_quick_access("Documents")? {
+///         println!("Found item in Quick Access");
+///     }
+///
+///     // Check for items in a specific location
+///     if is_in_quick_access("C:\\Projects\\")? {
+///         println!("Found items from Projects folder");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn is_in_quick_access(keyword: &str) -> WincentResult<bool> {
+    let items = get_quick_access_items()?;
+
+    Ok(items.iter().any(|item| item.contains(keyword)))
+}
+
+/// Picks the Quick Access category containing a path, frequent folders first.
+pub(crate) fn find_category(
+    path: &str,
+    recent_files: &[String],
+    frequent_folders: &[String],
+) -> Option<QuickAccess> {
+    if frequent_folders.iter().any(|item| paths_equal(item, path)) {
+        Some(QuickAccess::FrequentFolders)
+    } else if recent_files.iter().any(|item| paths_equal(item, path)) {
+        Some(QuickAccess::RecentFiles)
+    } else {
+        None
+    }
+}
+
+/// Finds which Quick Access category contains a path.
+///
+/// Unlike the keyword checks, the whole path must match, ignoring case and trailing
+/// separators. A path listed in both categories is reported as
+/// [`QuickAccess::FrequentFolders`].
+///
+/// # Arguments
+///
+/// * `path` - The full path of the file or folder to look for
+///
+/// # Returns
+///
+/// Returns the category the path was found in, or `None` if it is not in Quick Access.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::find_in_quick_access, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     match find_in_quick_access("C:\\Projects")? {
+///         Some(QuickAccess::FrequentFolders) => println!("Folder can be unpinned"),
+///         Some(QuickAccess::RecentFiles) => println!("File can be removed"),
+///         _ => println!("Not in Quick Access"),
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn find_in_quick_access(path: &str) -> WincentResult<Option<QuickAccess>> {
+    if path.is_empty() {
+        return Err(WincentError::InvalidPath("Empty path provided".to_string()));
+    }
+
+    let recent_files = get_recent_files()?;
+    let frequent_folders = get_frequent_folders()?;
+
+    Ok(find_category(path, &recent_files, &frequent_folders))
+}
+
+/// Gets all Quick Access items located at or below a root directory.
+///
+/// Unlike the keyword checks, this matches whole path components, so `C:\Projects2`
+/// is not considered to be under `C:\Projects`. Comparison ignores case and
+/// trailing separators.
+///
+/// # Arguments
+///
+/// * `root` - The directory to search under
+///
+/// # Returns
+///
+/// Returns the paths of recent files and frequent folders under `root`.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::items_under, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     for item in items_under("C:\\Projects")? {
+///         println!("Project item: {}", item);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn items_under(root: &str) -> WincentResult<Vec<String>> {
+    if root.is_empty() {
+        return Err(WincentError::InvalidPath("Empty path provided".to_string()));
+    }
+
+    query_with_filter(QuickAccess::All, |item| is_path_under(item, root))
+}
+
+/// Gets a counter increasing with every change this process makes to Quick Access.
+///
+/// Components holding a previously queried list can compare versions to tell whether
+/// it may be stale without querying again. Changes made by other processes or by the
+/// user in Explorer are not counted.
+///
+/// # Returns
+///
+/// Returns the current version, starting at `0`.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::{get_recent_files, state_version}, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let version = state_version();
+///     let files = get_recent_files()?;
+///
+///     // ...later
+///     if state_version() != version {
+///         println!("{} files listed before, refreshing", files.len());
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn state_version() -> u64 {
+    utils::state_version()
+}
+
+/// Number of item snapshots kept for [`items_changed_since`].
+const MAX_SNAPSHOTS: usize = 8;
+
+/// Item snapshots by state version, oldest first.
+static SNAPSHOTS: Mutex<VecDeque<(u64, Vec<String>)>> = Mutex::new(VecDeque::new());
+
+/// Stores a snapshot, replacing one of the same version and dropping the oldest beyond
+/// `max` snapshots.
+pub(crate) fn push_snapshot(
+    history: &mut VecDeque<(u64, Vec<String>)>,
+    version: u64,
+    items: Vec<String>,
+    max: usize,
+) {
+    history.retain(|(kept, _)| *kept != version);
+    history.push_back((version, items));
+    while history.len() > max {
+        history.pop_front();
+    }
+}
+
+/// Lists the items added and removed between two lists, comparing them like paths.
+pub(crate) fn diff_items(before: &[String], after: &[String]) -> (Vec<String>, Vec<String>) {
+    let added = after
+        .iter()
+        .filter(|item| !before.iter().any(|b| paths_equal(b, item)))
+        .cloned()
+        .collect();
+    let removed = before
+        .iter()
+        .filter(|item| !after.iter().any(|a| paths_equal(a, item)))
+        .cloned()
+        .collect();
+
+    (added, removed)
+}
+
+/// Queries all Quick Access items and keeps them as the snapshot of the current version.
+fn take_snapshot() -> WincentResult<(u64, Vec<String>)> {
+    // Read before querying, a change made meanwhile shows up again in the next diff
+    let version = state_version();
+    let items = get_quick_access_items()?;
+
+    let mut history = SNAPSHOTS.lock().unwrap_or_else(|e| e.into_inner());
+    push_snapshot(&mut history, version, items.clone(), MAX_SNAPSHOTS);
+
+    Ok((version, items))
+}
+
+/// Gets all Quick Access items along with the state version they belong to.
+///
+/// The items are kept as a baseline for [`items_changed_since`], only the last few
+/// versions are kept.
+///
+/// # Returns
+///
+/// Returns the state version and the Quick Access items.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::snapshot_items, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let (version, items) = snapshot_items()?;
+///     println!("{} items at version {}", items.len(), version);
+///     Ok(())
+/// }
+/// ```
+pub fn snapshot_items() -> WincentResult<(u64, Vec<String>)> {
+    take_snapshot()
+}
+
+/// Gets the Quick Access items added and removed since a state version.
+///
+/// Nothing is queried while the state version did not change. Otherwise the items are
+/// queried and compared with the snapshot kept for `since_version`, which must come from
+/// [`snapshot_items`] or a previous diff. As with [`state_version`], changes made by other
+/// processes are only picked up along with a change made through wincent.
+///
+/// # Arguments
+///
+/// * `since_version` - The state version the caller's items belong to
+///
+/// # Returns
+///
+/// Returns `None` if nothing changed since `since_version`, the diff otherwise. The diff
+/// can be empty if the changes cancelled each other out.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::{items_changed_since, snapshot_items}, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let (mut version, _items) = snapshot_items()?;
+///
+///     // ...on each UI refresh
+///     if let Some(diff) = items_changed_since(version)? {
+///         println!("+{} -{}", diff.added.len(), diff.removed.len());
+///         version = diff.version;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn items_changed_since(since_version: u64) -> WincentResult<Option<ItemDiff>> {
+    if state_version() == since_version {
+        return Ok(None);
+    }
+
+    let before = {
+        let history = SNAPSHOTS.lock().unwrap_or_else(|e| e.into_inner());
+        history
+            .iter()
+            .find(|(version, _)| *version == since_version)
+            .map(|(_, items)| items.clone())
+    }
+    .ok_or_else(|| {
+        WincentError::InvalidArgument(format!(
+            "No snapshot kept for state version {}, take a new one with snapshot_items",
+            since_version
+        ))
+    })?;
+
+    let (version, after) = take_snapshot()?;
+    let (added, removed) = diff_items(&before, &after);
+
+    Ok(Some(ItemDiff {
+        version,
+        added,
+        removed,
+    }))
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Hashes bytes with 64-bit FNV-1a, which unlike the std hashers is stable across
+/// processes, machines and Rust versions.
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Hashes the normalized, sorted and deduplicated items together with the visibility settings.
+pub(crate) fn compute_fingerprint(
+    items: &[String],
+    recent_visible: bool,
+    frequent_visible: bool,
+) -> u64 {
+    let mut normalized: Vec<String> = items
+        .iter()
+        .map(|item| normalize_path(item).to_lowercase())
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+
+    let hash = normalized.iter().fold(FNV_OFFSET_BASIS, |hash, item| {
+        // The separator keeps ["ab", "c"] and ["a", "bc"] apart
+        fnv1a(fnv1a(hash, item.as_bytes()), &[0])
+    });
+
+    fnv1a(
+        hash,
+        &[u8::from(recent_visible), u8::from(frequent_visible)],
+    )
+}
+
+/// Computes a fingerprint of the current Quick Access state.
+///
+/// The fingerprint covers all Quick Access items, compared like paths (ignoring case,
+/// separators style and order), and whether recent files and frequent folders are shown.
+/// Identical states yield identical fingerprints, across processes and machines, so sync
+/// tools can detect that nothing changed without exchanging the full lists.
+///
+/// # Returns
+///
+/// Returns the 64-bit fingerprint.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{query::fingerprint, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let before = fingerprint()?;
+///     // ...later
+///     if fingerprint()? == before {
+///         println!("Quick Access unchanged, nothing to sync");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn fingerprint() -> WincentResult<u64> {
+    let items = get_quick_access_items()?;
+
+    Ok(compute_fingerprint(
+        &items,
+        is_recent_files_visiable()?,
+        is_frequent_folders_visible()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{
+        cleanup_test_env, create_test_file, create_test_shortcut, setup_test_env,
+    };
+
+    fn mock_output(code: u32, stdout: &str, stderr: &str) -> std::process::Output {
+        use std::os::windows::process::ExitStatusExt;
+
+        std::process::Output {
+            status: std::process::ExitStatus::from_raw(code),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_parse_empty_output() -> WincentResult<()> {
+        assert!(parse_output_to_strings(mock_output(0, "", ""))?.is_empty());
+        assert!(
+            parse_output_to_strings(mock_output(0, "\r\n  \r\n", ""))?.is_empty(),
+            "Blank lines should not become items"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_output_lines() -> WincentResult<()> {
+        let items = parse_output_to_strings(mock_output(0, "C:\\a.txt\r\n\r\nC:\\b\n", ""))?;
+        assert_eq!(items, vec!["C:\\a.txt".to_string(), "C:\\b".to_string()]);
+
+        assert!(matches!(
+            parse_output_to_strings(mock_output(1, "", "failed")),
+            Err(WincentError::ScriptFailed(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_parse_output_drops_banner_lines() -> WincentResult<()> {
+        let stdout = concat!(
+            "Windows PowerShell\r\n",
+            "Copyright (C) Microsoft Corporation. All rights reserved.\r\n",
+            "WARNING: The module could not be loaded.\r\n",
+            "C:\\Docs\\a.txt\r\n",
+            "\\\\server\\share\\b.txt\r\n",
+            "::{645FF040-5081-101B-9F08-00AA002F954E}\r\n",
+            "D:/c.txt\r\n",
+            "https://contoso.sharepoint.com/sites/team/Shared Documents/d.docx\r\n",
+            "WARNING: See https://aka.ms/pscore6 for details.\r\n",
+        );
+
+        let items = parse_output_to_paths(mock_output(0, stdout, ""))?;
+        assert_eq!(
+            items,
+            vec![
+                "C:\\Docs\\a.txt".to_string(),
+                "\\\\server\\share\\b.txt".to_string(),
+                "::{645FF040-5081-101B-9F08-00AA002F954E}".to_string(),
+                "D:/c.txt".to_string(),
+                "https://contoso.sharepoint.com/sites/team/Shared Documents/d.docx".to_string(),
+            ]
+        );
+
+        use crate::config::{set_config, WincentConfig};
+
+        let initial = get_config();
+        set_config(WincentConfig {
+            filter_query_output: false,
+            ..initial.clone()
+        });
+        let unfiltered = parse_output_to_paths(mock_output(0, stdout, ""));
+        set_config(initial);
+        assert_eq!(unfiltered?.len(), 9, "Filtering should be configurable");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_url_scheme() {
+        assert!(has_url_scheme(
+            "https://contoso-my.sharepoint.com/personal/a.docx"
+        ));
+        assert!(has_url_scheme("ms-word://open"));
+        assert!(!has_url_scheme("C://Docs/a.txt"));
+        assert!(!has_url_scheme("WARNING: See https://aka.ms/pscore6"));
+        assert!(!has_url_scheme("Copyright (C) Microsoft Corporation."));
+    }
+
+    #[test]
+    fn test_parse_output_keeps_spaces() -> WincentResult<()> {
+        let path = " C:\\my  folder\\ notes .txt ";
+        let items = parse_output_to_strings(mock_output(0, &format!("{}\r\n", path), ""))?;
+        assert_eq!(items, vec![path.to_string()], "Spaces should be preserved");
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_recent_files() -> WincentResult<()> {
+        let files = query_recent_with_ps_script(QuickAccess::RecentFiles)?;
+
+        if !files.is_empty() {
+            assert!(
+                files.iter().all(|path| !path.is_empty()),
+                "Paths should not be empty"
+            );
+
+            for path in &files {
+                assert!(
+                    path.contains(":\\"),
                     "Path should be a valid Windows path format: {}",
                     path
                 );
@@ -359,6 +2309,690 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_list_shortcut_targets() -> WincentResult<()> {
+        let test_dir = setup_test_env()?;
+        let target = create_test_file(&test_dir, "target.txt", "content")?;
+        let lnk_dir = test_dir.join("links");
+        std::fs::create_dir_all(&lnk_dir)?;
+        create_test_shortcut(&lnk_dir, "target.lnk", &target)?;
+        create_test_file(&lnk_dir, "not_a_shortcut.txt", "content")?;
+
+        let targets = list_shortcut_targets(&lnk_dir)?;
+        assert_eq!(targets, vec![target.to_str().unwrap().to_string()]);
+
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_shortcuts_to_same_target() -> WincentResult<()> {
+        let test_dir = setup_test_env()?;
+        let target = create_test_file(&test_dir, "target.txt", "content")?;
+        let target_path = target.to_str().unwrap();
+        let lnk_dir = test_dir.join("links");
+        std::fs::create_dir_all(&lnk_dir)?;
+        let first = create_test_shortcut(&lnk_dir, "target.lnk", &target)?;
+        let second = create_test_shortcut(&lnk_dir, "target (2).lnk", &target)?;
+
+        for shortcut in [&first, &second] {
+            assert!(paths_equal(
+                &resolve_if_shortcut(shortcut.to_str().unwrap())?,
+                target_path
+            ));
+        }
+        assert_eq!(resolve_if_shortcut(target_path)?, target_path);
+
+        let shortcuts = list_shortcuts(&lnk_dir)?;
+        assert_eq!(shortcuts.len(), 2);
+        assert!(contains_target(&shortcuts, target_path));
+        assert!(contains_target(
+            &shortcuts,
+            &resolve_if_shortcut(second.to_str().unwrap())?
+        ));
+        assert!(!contains_target(
+            &shortcuts,
+            test_dir.join("other.txt").to_str().unwrap()
+        ));
+
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_recent_with_shortcuts() -> WincentResult<()> {
+        let files = query_recent_with_shortcuts(QuickAccess::RecentFiles)?;
+        assert!(files.iter().all(|path| Path::new(path).is_file()));
+
+        let folders = query_recent_with_shortcuts(QuickAccess::FrequentFolders)?;
+        assert!(folders.iter().all(|path| Path::new(path).is_dir()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_recent_files_top() -> WincentResult<()> {
+        let all = query_recent_with_ps_script(QuickAccess::RecentFiles)?;
+        let top = get_recent_files_top(3)?;
+
+        assert!(top.len() <= 3);
+        assert_eq!(top[..], all[..top.len()], "Top files should keep the order");
+        assert!(get_recent_files_top(0)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_virtual_path() {
+        let virtual_paths = [
+            "::{645FF040-5081-101B-9F08-00AA002F954E}",
+            "::{20D04FE0-3AEA-1069-A2D8-08002B30309D}\\C:\\file.txt",
+            "https://contoso.sharepoint.com/doc.docx",
+            "",
+        ];
+        for path in virtual_paths {
+            assert!(is_virtual_path(path), "Should be virtual: {}", path);
+        }
+
+        let filesystem_paths = ["C:\\file.txt", "d:/file.txt", "\\\\server\\share\\file.txt"];
+        for path in filesystem_paths {
+            assert!(
+                !is_virtual_path(path),
+                "Should be a filesystem path: {}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_rank_by_position() {
+        let ranked = rank_by_position(vec!["C:\\a".to_string(), "C:\\b".to_string()]);
+        assert_eq!(
+            ranked,
+            vec![("C:\\a".to_string(), 1), ("C:\\b".to_string(), 2)]
+        );
+        assert!(rank_by_position(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_order_pinned_first() {
+        let pinned = vec!["C:\\Pinned2".to_string(), "C:\\Pinned1".to_string()];
+        let frequent = vec![
+            "C:\\Frequent".to_string(),
+            "c:\\pinned1\\".to_string(),
+            "C:\\Pinned2".to_string(),
+        ];
+
+        let ordered = order_pinned_first(pinned, frequent);
+        let paths: Vec<&str> = ordered.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["C:\\Pinned2", "C:\\Pinned1", "C:\\Frequent"]);
+        assert!(
+            ordered[..2].iter().all(|f| f.pinned),
+            "Pinned should come first"
+        );
+        assert!(!ordered[2].pinned, "Frequent folders should follow");
+    }
+
+    #[test]
+    fn test_unpinned_only() {
+        let pinned = vec!["C:\\Pinned".to_string()];
+        let frequent = vec![
+            "C:\\Visited1".to_string(),
+            "c:\\pinned\\".to_string(),
+            "C:\\Visited2".to_string(),
+        ];
+
+        assert_eq!(
+            unpinned_only(order_pinned_first(pinned, frequent)),
+            vec!["C:\\Visited1".to_string(), "C:\\Visited2".to_string()]
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_get_unpinned_frequent_folders() -> WincentResult<()> {
+        use crate::handle::{add_to_frequent_folders, remove_from_frequent_folders};
+
+        let test_dir = setup_test_env()?;
+        let folder = test_dir.join("pinned");
+        std::fs::create_dir_all(&folder)?;
+        let folder = folder.to_str().unwrap();
+
+        add_to_frequent_folders(folder)?;
+        let unpinned = get_unpinned_frequent_folders()?;
+        let pinned = get_pinned_folders()?;
+
+        assert!(
+            !unpinned.iter().any(|item| paths_equal(item, folder)),
+            "Pinned folder should be excluded"
+        );
+        assert!(
+            !unpinned
+                .iter()
+                .any(|item| pinned.iter().any(|p| paths_equal(p, item))),
+            "No pinned folder should be listed"
+        );
+
+        remove_from_frequent_folders(folder)?;
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn test_available_verbs_pinned_folder() -> WincentResult<()> {
+        use crate::handle::{add_to_frequent_folders, remove_from_frequent_folders};
+
+        let test_dir = setup_test_env()?;
+        let folder = test_dir.join("verbs");
+        std::fs::create_dir_all(&folder)?;
+        let folder = folder.to_str().unwrap();
+
+        assert_eq!(get_frequent_folder_verbs(folder)?, None);
+
+        add_to_frequent_folders(folder)?;
+        let verbs = available_verbs(folder)?;
+        assert!(
+            verbs.iter().any(|verb| verb == "unpinfromhome"),
+            "Pinned folder should offer the canonical unpin verb: {:?}",
+            verbs
+        );
+        assert!(verbs.iter().all(|verb| !verb.contains('&')));
+
+        remove_from_frequent_folders(folder)?;
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_available_verbs_rejects_invalid_path() {
+        for path in ["", "C:\\dir\\a\"b", "C:\\dir\\a|b"] {
+            assert!(
+                matches!(available_verbs(path), Err(WincentError::InvalidPath(_))),
+                "Should reject {:?}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_contains_keyword() {
+        assert!(contains_keyword("C:\\Projects\\App", "project"));
+        assert!(contains_keyword("D:\\old-PROJECT.txt", "Project"));
+        assert!(!contains_keyword("C:\\Docs\\report.docx", "project"));
+    }
+
+    #[test]
+    fn test_find_matching_items_rejects_empty_keyword() {
+        assert!(matches!(
+            find_matching_items("", QuickAccess::All),
+            Err(WincentError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_volume_of() {
+        assert_eq!(volume_of("c:\\Docs\\a.txt"), "C:");
+        assert_eq!(volume_of("D:/data"), "D:");
+        assert_eq!(volume_of("\\\\server\\share\\doc.txt"), "\\\\server\\share");
+        assert_eq!(volume_of("\\\\server\\share"), "\\\\server\\share");
+        assert_eq!(volume_of("\\\\server"), OTHER_VOLUME);
+        assert_eq!(
+            volume_of("::{645FF040-5081-101B-9F08-00AA002F954E}"),
+            OTHER_VOLUME
+        );
+    }
+
+    #[test]
+    fn test_group_items_by_volume() {
+        let items = vec![
+            "C:\\Docs\\a.txt".to_string(),
+            "D:\\Media".to_string(),
+            "C:\\Projects".to_string(),
+            "\\\\nas\\public\\b.txt".to_string(),
+            "::{645FF040-5081-101B-9F08-00AA002F954E}".to_string(),
+        ];
+
+        let groups = group_items_by_volume(items);
+        assert_eq!(groups.len(), 4);
+        assert_eq!(
+            groups["C:"],
+            vec!["C:\\Docs\\a.txt".to_string(), "C:\\Projects".to_string()]
+        );
+        assert_eq!(groups["D:"], vec!["D:\\Media".to_string()]);
+        assert_eq!(groups["\\\\nas\\public"].len(), 1);
+        assert_eq!(groups[OTHER_VOLUME].len(), 1);
+    }
+
+    #[test]
+    fn test_probe_reachability() {
+        let items = vec![
+            "C:\\Fast".to_string(),
+            "\\\\offline\\share".to_string(),
+            "C:\\Missing".to_string(),
+            "::{645FF040-5081-101B-9F08-00AA002F954E}".to_string(),
+        ];
+
+        let report = probe_reachability(items, Duration::from_millis(200), |path| {
+            if path.starts_with("\\\\offline") {
+                std::thread::sleep(Duration::from_secs(5));
+            }
+            path != "C:\\Missing"
+        });
+
+        assert_eq!(
+            report.reachable,
+            vec![
+                "C:\\Fast".to_string(),
+                "::{645FF040-5081-101B-9F08-00AA002F954E}".to_string()
+            ]
+        );
+        assert_eq!(
+            report.unreachable,
+            vec!["\\\\offline\\share".to_string(), "C:\\Missing".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_probe_reachability_bounds_threads() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let items: Vec<String> = (0..40)
+            .map(|i| format!("\\\\server{}\\share\\a", i % 20))
+            .collect();
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let (running_probe, peak_probe) = (Arc::clone(&running), Arc::clone(&peak));
+        let report = probe_reachability(items, Duration::from_secs(10), move |_| {
+            let now = running_probe.fetch_add(1, Ordering::SeqCst) + 1;
+            peak_probe.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(20));
+            running_probe.fetch_sub(1, Ordering::SeqCst);
+            true
+        });
+
+        assert_eq!(report.reachable.len(), 40);
+        assert!(
+            peak.load(Ordering::SeqCst) <= MAX_PROBE_WORKERS,
+            "At most {} probes should run at once",
+            MAX_PROBE_WORKERS
+        );
+    }
+
+    #[test]
+    fn test_latest_existing() {
+        let now = SystemTime::now();
+        let entry = |target: &str, age: u64| ShortcutEntry {
+            lnk_path: format!("{}.lnk", target),
+            target: target.to_string(),
+            modified: now - Duration::from_secs(age),
+        };
+        let shortcuts = vec![
+            entry("C:\\Docs\\old.txt", 300),
+            entry("C:\\Deleted\\new.txt", 10),
+            entry("C:\\Projects", 60),
+        ];
+
+        let latest = latest_existing(shortcuts, |target| !target.contains("Deleted"));
+        assert_eq!(
+            latest,
+            Some(("C:\\Projects".to_string(), now - Duration::from_secs(60)))
+        );
+
+        assert_eq!(latest_existing(Vec::new(), |_| true), None);
+    }
+
+    #[test]
+    fn test_used_between() {
+        let now = SystemTime::now();
+        let ago = |secs: u64| now - Duration::from_secs(secs);
+        let entry = |target: &str, age: u64| ShortcutEntry {
+            lnk_path: format!("{}.lnk", target),
+            target: target.to_string(),
+            modified: ago(age),
+        };
+        let shortcuts = vec![
+            entry("C:\\Docs\\old.txt", 600),
+            entry("C:\\Docs\\edge.txt", 300),
+            entry("C:\\Docs\\new.txt", 10),
+            entry("C:\\Docs\\reopened.txt", 900),
+            entry("C:\\Docs\\reopened.txt", 100),
+        ];
+        let items = vec![
+            "C:\\Docs\\new.txt".to_string(),
+            "C:\\Docs\\reopened.txt".to_string(),
+            "C:\\Docs\\edge.txt".to_string(),
+            "C:\\Docs\\untimed.txt".to_string(),
+            "C:\\Docs\\old.txt".to_string(),
+        ];
+
+        assert_eq!(
+            used_between(items, &shortcuts, ago(300), ago(50)),
+            vec![
+                "C:\\Docs\\reopened.txt".to_string(),
+                "C:\\Docs\\edge.txt".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recent_files_between_rejects_reversed_range() {
+        let now = SystemTime::now();
+        assert!(matches!(
+            recent_files_between(now, now - Duration::from_secs(1)),
+            Err(WincentError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_order_typed_paths() {
+        let values = vec![
+            ("url10".to_string(), "C:\\Tenth".to_string()),
+            ("url2".to_string(), "D:\\Second".to_string()),
+            ("(Default)".to_string(), "ignored".to_string()),
+            ("URL1".to_string(), "C:\\First".to_string()),
+            ("urlx".to_string(), "ignored".to_string()),
+        ];
+
+        assert_eq!(
+            order_typed_paths(values),
+            vec![
+                "C:\\First".to_string(),
+                "D:\\Second".to_string(),
+                "C:\\Tenth".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_poll_until() -> WincentResult<()> {
+        let mut runs = 0;
+        let satisfied = poll_until(Duration::from_secs(5), Duration::from_millis(1), || {
+            runs += 1;
+            Ok(runs == 3)
+        })?;
+        assert!(satisfied);
+        assert_eq!(runs, 3);
+
+        let mut runs = 0;
+        let satisfied = poll_until(Duration::ZERO, Duration::from_millis(1), || {
+            runs += 1;
+            Ok(false)
+        })?;
+        assert!(!satisfied);
+        assert_eq!(runs, 1, "Should check once even without time left");
+
+        let result = poll_until(Duration::from_secs(5), Duration::from_millis(1), || {
+            Err(WincentError::ScriptFailed("failed".to_string()))
+        });
+        assert!(matches!(result, Err(WincentError::ScriptFailed(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_snapshot_is_bounded() {
+        let mut history = VecDeque::new();
+        for version in 0..5 {
+            push_snapshot(&mut history, version, vec![format!("C:\\v{}", version)], 3);
+        }
+        push_snapshot(&mut history, 4, vec!["C:\\latest".to_string()], 3);
+
+        let versions: Vec<u64> = history.iter().map(|(version, _)| *version).collect();
+        assert_eq!(versions, vec![2, 3, 4]);
+        assert_eq!(history.back().unwrap().1, vec!["C:\\latest".to_string()]);
+    }
+
+    #[test]
+    fn test_diff_items() {
+        let before = vec!["C:\\Projects".to_string(), "C:\\Docs\\a.txt".to_string()];
+        let after = vec!["c:/projects/".to_string(), "D:\\Media".to_string()];
+
+        let (added, removed) = diff_items(&before, &after);
+        assert_eq!(added, vec!["D:\\Media".to_string()]);
+        assert_eq!(removed, vec!["C:\\Docs\\a.txt".to_string()]);
+
+        let (added, removed) = diff_items(&before, &before);
+        assert!(added.is_empty() && removed.is_empty());
+    }
+
+    #[test]
+    fn test_items_changed_since_unknown_version() {
+        let result = items_changed_since(state_version().wrapping_add(1_000_000));
+        assert!(matches!(result, Err(WincentError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_fnv1a_is_stable() {
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b""), 0xCBF2_9CE4_8422_2325);
+        assert_eq!(fnv1a(FNV_OFFSET_BASIS, b"a"), 0xAF63_DC4C_8601_EC8C);
+    }
+
+    #[test]
+    fn test_compute_fingerprint() {
+        let items = vec!["C:\\Projects".to_string(), "D:\\Docs\\a.txt".to_string()];
+        let same = vec![
+            "d:/docs/A.TXT".to_string(),
+            "C:\\Projects\\".to_string(),
+            "c:\\projects".to_string(),
+        ];
+
+        let fingerprint = compute_fingerprint(&items, true, true);
+        assert_eq!(fingerprint, compute_fingerprint(&same, true, true));
+        assert_ne!(fingerprint, compute_fingerprint(&items, false, true));
+        assert_ne!(fingerprint, compute_fingerprint(&items, true, false));
+        assert_ne!(fingerprint, compute_fingerprint(&items[..1], true, true));
+
+        let split = vec!["C:\\ab".to_string(), "c".to_string()];
+        let joined = vec!["C:\\a".to_string(), "bc".to_string()];
+        assert_ne!(
+            compute_fingerprint(&split, true, true),
+            compute_fingerprint(&joined, true, true)
+        );
+    }
+
+    #[test]
+    fn test_paginate() {
+        let items: Vec<String> = (0..5).map(|i| format!("C:\\item{}", i)).collect();
+
+        let (page, total) = paginate(items.clone(), 1, 2);
+        assert_eq!(page, vec!["C:\\item1".to_string(), "C:\\item2".to_string()]);
+        assert_eq!(total, 5);
+
+        let (page, _) = paginate(items.clone(), 4, 10);
+        assert_eq!(page.len(), 1, "Last page should be truncated");
+
+        let (page, total) = paginate(items.clone(), 10, 2);
+        assert!(page.is_empty(), "Page past the end should be empty");
+        assert_eq!(total, 5);
+
+        let (page, _) = paginate(items, usize::MAX, usize::MAX);
+        assert!(page.is_empty(), "Huge bounds should not overflow");
+    }
+
+    #[test]
+    fn test_is_compound_header() {
+        let mut header = vec![0u8; COMPOUND_HEADER_SIZE];
+        header[..8].copy_from_slice(&COMPOUND_SIGNATURE);
+        header[28..30].copy_from_slice(&0xFFFEu16.to_le_bytes());
+        header[30..32].copy_from_slice(&9u16.to_le_bytes());
+        assert!(
+            is_compound_header(&header),
+            "Version 3 header should be valid"
+        );
+
+        header[30..32].copy_from_slice(&12u16.to_le_bytes());
+        assert!(
+            is_compound_header(&header),
+            "Version 4 header should be valid"
+        );
+
+        header[30..32].copy_from_slice(&7u16.to_le_bytes());
+        assert!(
+            !is_compound_header(&header),
+            "Bad sector size should be invalid"
+        );
+
+        header[30..32].copy_from_slice(&9u16.to_le_bytes());
+        header[0] = 0;
+        assert!(
+            !is_compound_header(&header),
+            "Bad signature should be invalid"
+        );
+
+        assert!(
+            !is_compound_header(&COMPOUND_SIGNATURE),
+            "Truncated header should be invalid"
+        );
+        assert!(!is_compound_header(&[]), "Empty file should be invalid");
+    }
+
+    #[test]
+    fn test_validate_jumplist() -> WincentResult<()> {
+        let health = validate_jumplist()?;
+        assert_ne!(
+            health,
+            JumplistHealth::Corrupt,
+            "Jump list on a test machine should not be corrupt"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_jumplist_health() -> WincentResult<()> {
+        let dir = tempfile::tempdir()?;
+
+        let missing = dir.path().join("missing.automaticDestinations-ms");
+        assert_eq!(get_jumplist_health(&missing)?, JumplistHealth::Missing);
+
+        let truncated = dir.path().join("truncated.automaticDestinations-ms");
+        std::fs::write(&truncated, COMPOUND_SIGNATURE)?;
+        assert_eq!(
+            get_jumplist_health(&truncated)?,
+            JumplistHealth::Missing,
+            "Truncated files should not be reported as corrupt"
+        );
+
+        let mut header = vec![0u8; COMPOUND_HEADER_SIZE];
+        header[..8].copy_from_slice(&COMPOUND_SIGNATURE);
+        header[28..30].copy_from_slice(&0xFFFEu16.to_le_bytes());
+        header[30..32].copy_from_slice(&9u16.to_le_bytes());
+        let valid = dir.path().join("valid.automaticDestinations-ms");
+        std::fs::write(&valid, &header)?;
+        assert_eq!(get_jumplist_health(&valid)?, JumplistHealth::Valid);
+
+        let corrupt = dir.path().join("corrupt.automaticDestinations-ms");
+        std::fs::write(&corrupt, vec![0xAAu8; COMPOUND_HEADER_SIZE])?;
+        assert_eq!(get_jumplist_health(&corrupt)?, JumplistHealth::Corrupt);
+
+        // Extended past the limit without writing the content
+        let oversized = dir.path().join("oversized.automaticDestinations-ms");
+        let mut file = std::fs::File::create(&oversized)?;
+        std::io::Write::write_all(&mut file, &header)?;
+        file.set_len(MAX_JUMPLIST_SIZE + 1)?;
+        drop(file);
+        assert_eq!(
+            get_jumplist_health(&oversized)?,
+            JumplistHealth::Corrupt,
+            "Oversized files should be reported as corrupt so they can be repaired"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_category() {
+        let recent = vec![
+            "C:\\Docs\\report.docx".to_string(),
+            "C:\\Shared".to_string(),
+        ];
+        let frequent = vec!["C:\\Projects".to_string(), "C:\\Shared".to_string()];
+
+        assert_eq!(
+            find_category("c:\\docs\\REPORT.docx", &recent, &frequent),
+            Some(QuickAccess::RecentFiles)
+        );
+        assert_eq!(
+            find_category("C:\\Projects\\", &recent, &frequent),
+            Some(QuickAccess::FrequentFolders)
+        );
+        assert_eq!(
+            find_category("C:\\Shared", &recent, &frequent),
+            Some(QuickAccess::FrequentFolders),
+            "Frequent folders should take precedence"
+        );
+        assert_eq!(find_category("C:\\Docs", &recent, &frequent), None);
+    }
+
+    #[test]
+    fn test_with_normalized() {
+        let pairs = with_normalized(vec!["C:\\Projects\\".to_string(), "D:\\".to_string()]);
+        assert_eq!(
+            pairs,
+            vec![
+                ("C:\\Projects\\".to_string(), "C:\\Projects".to_string()),
+                ("D:\\".to_string(), "D:\\".to_string()),
+            ],
+            "Trailing separator should only be dropped below the drive root"
+        );
+    }
+
+    #[test]
+    fn test_query_with_timeout() -> WincentResult<()> {
+        let items = query_recent_with_timeout(QuickAccess::RecentFiles, Duration::from_secs(30))?;
+        assert_eq!(
+            items,
+            query_recent_with_ps_script(QuickAccess::RecentFiles)?
+        );
+
+        assert!(matches!(
+            query_recent_with_timeout(QuickAccess::RecentFiles, Duration::ZERO),
+            Err(WincentError::Timeout(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_query_with_filter() -> WincentResult<()> {
+        let all = get_items_by_type(QuickAccess::RecentFiles)?;
+        assert_eq!(query_with_filter(QuickAccess::RecentFiles, |_| true)?, all);
+        assert!(query_with_filter(QuickAccess::RecentFiles, |_| false)?.is_empty());
+
+        let filtered = query_with_filter(QuickAccess::RecentFiles, |path| path.ends_with(".txt"))?;
+        assert!(filtered.iter().all(|path| path.ends_with(".txt")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_items_under() -> WincentResult<()> {
+        assert!(items_under("").is_err(), "Should fail with empty root");
+
+        let items = items_under("C:\\")?;
+        assert!(items
+            .iter()
+            .all(|item| item.to_lowercase().starts_with("c:\\")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_last_modified() -> WincentResult<()> {
+        let recent = get_last_modified(QuickAccess::RecentFiles)?;
+        let frequent = get_last_modified(QuickAccess::FrequentFolders)?;
+        let all = get_last_modified(QuickAccess::All)?;
+
+        assert_eq!(
+            all,
+            recent.max(frequent),
+            "All should report the latest time"
+        );
+
+        Ok(())
+    }
+
     #[test_log::test]
     fn test_query_quick_access() -> WincentResult<()> {
         let items = query_recent_with_ps_script(QuickAccess::All)?;
@@ -1,13 +1,36 @@
-use crate::{error::WincentError, WincentResult};
-use std::io::Write;
-use std::process::Command;
-use tempfile::Builder;
+use crate::{config, error::WincentError, metrics, WincentResult};
+use std::io::{Read, Write};
+use std::os::windows::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+use tempfile::{Builder, NamedTempFile};
+use windows::Win32::System::Threading::CREATE_NO_WINDOW;
+
+const UTF8_BOM: [u8; 3] = [0xEF, 0xBB, 0xBF];
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+/// Longest `-EncodedCommand` argument passed, keeping well below the 32767 characters
+/// Windows allows on a command line.
+const MAX_ENCODED_COMMAND_LENGTH: usize = 30_000;
+
+pub(crate) use crate::shell::FREQUENT_FOLDERS_NAMESPACE;
+
+/// Exit code of mutation scripts when the item to add is already present.
+pub(crate) const EXIT_ALREADY_EXISTS: i32 = 2;
+/// Exit code of mutation scripts when the item to remove is not listed.
+pub(crate) const EXIT_NOT_FOUND: i32 = 3;
+/// Exit code of the query feasibility check when the query did not finish in time.
+pub(crate) const EXIT_TIMED_OUT: i32 = 4;
+/// Exit code of the query feasibility check when the Quick Access namespace is unavailable.
+pub(crate) const EXIT_NAMESPACE_UNAVAILABLE: i32 = 5;
 
 pub(crate) enum Script {
     RefreshExplorer,
     QueryQuickAccess,
     QuertRecentFile,
     QueryFrequentFolder,
+    QueryPinnedFolder,
+    QueryRecentFileTop,
     RemoveRecentFile,
     PinToFrequentFolder,
     UnpinFromFrequentFolder,
@@ -15,6 +38,39 @@ pub(crate) enum Script {
     CheckPinUnpinFeasible,
 }
 
+impl Script {
+    /// Returns the operation name reported to metrics sinks.
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Script::RefreshExplorer => "RefreshExplorer",
+            Script::QueryQuickAccess => "QueryQuickAccess",
+            Script::QuertRecentFile => "QueryRecentFile",
+            Script::QueryFrequentFolder => "QueryFrequentFolder",
+            Script::QueryPinnedFolder => "QueryPinnedFolder",
+            Script::QueryRecentFileTop => "QueryRecentFileTop",
+            Script::RemoveRecentFile => "RemoveRecentFile",
+            Script::PinToFrequentFolder => "PinToFrequentFolder",
+            Script::UnpinFromFrequentFolder => "UnpinFromFrequentFolder",
+            Script::CheckQueryFeasible => "CheckQueryFeasible",
+            Script::CheckPinUnpinFeasible => "CheckPinUnpinFeasible",
+        }
+    }
+
+    /// Returns `true` for scripts whose content never depends on a parameter.
+    pub(crate) fn is_parameterless(&self) -> bool {
+        matches!(
+            self,
+            Script::RefreshExplorer
+                | Script::QueryQuickAccess
+                | Script::QuertRecentFile
+                | Script::QueryFrequentFolder
+                | Script::QueryPinnedFolder
+                | Script::CheckQueryFeasible
+                | Script::CheckPinUnpinFeasible
+        )
+    }
+}
+
 static REFRESH_EXPLORER: &str = r#"
     $OutputEncoding = [Console]::OutputEncoding = [System.Text.Encoding]::UTF8;
     $shellApplication = New-Object -ComObject Shell.Application;
@@ -47,29 +103,39 @@ static CHECK_QUERY_FEASIBLE: &str = r#"
 
     $scriptBlock = {
         $shell = New-Object -ComObject Shell.Application
-        $shell.Namespace('shell:::{679f85cb-0220-4080-b29b-5540cc05aab6}').Items() | ForEach-Object { $_.Path };
+        $namespace = $shell.Namespace('shell:::{679f85cb-0220-4080-b29b-5540cc05aab6}')
+        if (-not $namespace) { exit 1 }
+        $namespace.Items() | ForEach-Object { $_.Path };
     }.ToString()
 
     $arguments = "-Command & {$scriptBlock}"
     $process = Start-Process powershell -ArgumentList $arguments -NoNewWindow -PassThru
+    # Caching the handle keeps the exit code available once the process exits
+    $null = $process.Handle
 
     if (-not $process.WaitForExit($timeout * 1000)) {
         try {
             $process.Kill()
             Write-Error "Process execution timed out (${timeout}s), forcefully terminated"
-            exit 1
+            exit {timed_out}
         }
         catch {
             Write-Error "Error occurred while terminating process: $_"
             exit 1
         }
     }
+
+    if ($process.ExitCode -ne 0) {
+        Write-Error "Quick Access namespace is unavailable"
+        exit {namespace_unavailable}
+    }
 "#;
 
 static CHECK_PIN_UNPIN_FEASIBLE: &str = r#"
     $OutputEncoding = [Console]::OutputEncoding = [System.Text.Encoding]::UTF8;
 
-    $currentPath = $PSScriptRoot
+    # $PSScriptRoot is empty when the script is passed inline
+    $currentPath = if ($PSScriptRoot) { $PSScriptRoot } else { $env:TEMP }
 
     $scriptBlock = {
         param($scriptPath)
@@ -98,6 +164,24 @@ static CHECK_PIN_UNPIN_FEASIBLE: &str = r#"
     }
 "#;
 
+/// Quotes a value as a PowerShell single-quoted string literal.
+///
+/// Nothing is expanded inside single quotes, only the quote itself has to be doubled.
+/// PowerShell also accepts typographic single quotes as delimiters, so they are doubled too.
+pub(crate) fn quote_literal(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        if matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}') {
+            quoted.push(c);
+        }
+        quoted.push(c);
+    }
+    quoted.push('\'');
+
+    quoted
+}
+
 /// Generates PowerShell script content based on the specified method and optional parameters.
 pub(crate) fn get_script_content(method: Script, para: Option<&str>) -> WincentResult<String> {
     match method {
@@ -105,21 +189,35 @@ pub(crate) fn get_script_content(method: Script, para: Option<&str>) -> WincentR
         Script::QuertRecentFile => Ok(QUERY_RECENT_FILE.to_string()),
         Script::QueryFrequentFolder => Ok(QUERY_FREQUENT_FOLDER.to_string()),
         Script::QueryQuickAccess => Ok(QUERY_QUICK_ACCESS.to_string()),
+        Script::QueryPinnedFolder => Ok(format!(
+            r#"
+            $OutputEncoding = [Console]::OutputEncoding = [System.Text.Encoding]::UTF8;
+            $shell = New-Object -ComObject Shell.Application;
+            $shell.Namespace('{}').Items() | where {{ $_.ExtendedProperty('System.Home.IsPinned') -eq $true }} | ForEach-Object {{ $_.Path }};
+        "#,
+            FREQUENT_FOLDERS_NAMESPACE
+        )),
         Script::RemoveRecentFile => {
             if let Some(data) = para {
                 let content = format!(
                     r#"
                     $OutputEncoding = [Console]::OutputEncoding = [System.Text.Encoding]::UTF8;
                     $shell = New-Object -ComObject Shell.Application;
+                    $path = {};
                     $files = $shell.Namespace("shell:::{{679f85cb-0220-4080-b29b-5540cc05aab6}}").Items() | where {{$_.IsFolder -eq $false}};
-                    $target = $files | where {{$_.Path -eq "{}"}};
+                    $target = $files | where {{$_.Path -eq $path}};
+                    if ($null -eq $target) {{
+                        Write-Error "Item not found: $path";
+                        exit {}
+                    }}
                     $target.InvokeVerb("remove");
                 "#,
-                    data
+                    quote_literal(data),
+                    EXIT_NOT_FOUND
                 );
                 Ok(content)
             } else {
-                Err(WincentError::MissingParemeter)
+                Err(WincentError::MissingParameter)
             }
         }
         Script::PinToFrequentFolder => {
@@ -128,13 +226,21 @@ pub(crate) fn get_script_content(method: Script, para: Option<&str>) -> WincentR
                     r#"
                     $OutputEncoding = [Console]::OutputEncoding = [System.Text.Encoding]::UTF8;
                     $shell = New-Object -ComObject Shell.Application;
-                    $shell.Namespace("{}").Self.InvokeVerb("pintohome");
+                    $path = {};
+                    $pinned = $shell.Namespace('{}').Items() | where {{ $_.Path -eq $path -and $_.ExtendedProperty('System.Home.IsPinned') -eq $true }};
+                    if ($null -ne $pinned) {{
+                        Write-Error "Item already pinned: $path";
+                        exit {}
+                    }}
+                    $shell.Namespace($path).Self.InvokeVerb("pintohome");
                 "#,
-                    data
+                    quote_literal(data),
+                    FREQUENT_FOLDERS_NAMESPACE,
+                    EXIT_ALREADY_EXISTS
                 );
                 Ok(content)
             } else {
-                Err(WincentError::MissingParemeter)
+                Err(WincentError::MissingParameter)
             }
         }
         Script::UnpinFromFrequentFolder => {
@@ -143,18 +249,45 @@ pub(crate) fn get_script_content(method: Script, para: Option<&str>) -> WincentR
                     r#"
                     $OutputEncoding = [Console]::OutputEncoding = [System.Text.Encoding]::UTF8;
                     $shell = New-Object -ComObject Shell.Application;
+                    $path = {};
                     $folders = $shell.Namespace("shell:::{{3936E9E4-D92C-4EEE-A85A-BC16D5EA0819}}").Items();
-                    $target = $folders | Where-Object {{$_.Path -eq "{}"}};
+                    $target = $folders | Where-Object {{$_.Path -eq $path}};
+                    if ($null -eq $target) {{
+                        Write-Error "Item not found: $path";
+                        exit {}
+                    }}
                     $target.InvokeVerb("unpinfromhome");
                 "#,
-                    data
+                    quote_literal(data),
+                    EXIT_NOT_FOUND
                 );
                 Ok(content)
             } else {
-                Err(WincentError::MissingParemeter)
+                Err(WincentError::MissingParameter)
             }
         }
-        Script::CheckQueryFeasible => Ok(CHECK_QUERY_FEASIBLE.to_string()),
+        Script::QueryRecentFileTop => {
+            let count: usize = para
+                .ok_or(WincentError::MissingParameter)?
+                .parse()
+                .map_err(|_| {
+                    WincentError::InvalidArgument("Item count must be a number".to_string())
+                })?;
+            Ok(format!(
+                r#"
+                $OutputEncoding = [Console]::OutputEncoding = [System.Text.Encoding]::UTF8;
+                $shell = New-Object -ComObject Shell.Application;
+                $shell.Namespace('shell:::{{679f85cb-0220-4080-b29b-5540cc05aab6}}').Items() | where {{ $_.IsFolder -eq $false }} | Select-Object -First {} | ForEach-Object {{ $_.Path }};
+            "#,
+                count
+            ))
+        }
+        Script::CheckQueryFeasible => Ok(CHECK_QUERY_FEASIBLE
+            .replace("{timed_out}", &EXIT_TIMED_OUT.to_string())
+            .replace(
+                "{namespace_unavailable}",
+                &EXIT_NAMESPACE_UNAVAILABLE.to_string(),
+            )),
         Script::CheckPinUnpinFeasible => Ok(CHECK_PIN_UNPIN_FEASIBLE.to_string()),
     }
 }
@@ -164,7 +297,37 @@ pub(crate) fn execute_ps_script(
     method: Script,
     para: Option<&str>,
 ) -> WincentResult<std::process::Output> {
-    let content = get_script_content(method, para)?;
+    execute_with_metrics(method, para, None)
+}
+
+/// Executes a PowerShell script like [`execute_ps_script`], killing it after `timeout`.
+pub(crate) fn execute_ps_script_with_timeout(
+    method: Script,
+    para: Option<&str>,
+    timeout: Duration,
+) -> WincentResult<std::process::Output> {
+    execute_with_metrics(method, para, Some(timeout))
+}
+
+/// Runs a script and reports it to the metrics sink.
+fn execute_with_metrics(
+    method: Script,
+    para: Option<&str>,
+    timeout: Option<Duration>,
+) -> WincentResult<std::process::Output> {
+    let op = method.name();
+    let started = metrics::start();
+
+    let result = run_ps_script(method, para, timeout);
+
+    let success = matches!(&result, Ok(output) if output.status.success());
+    metrics::finish(op, started, success);
+
+    result
+}
+
+/// Writes script content as UTF-8 to a temporary `.ps1` file, optionally preceded by a BOM.
+fn write_script_file(content: &str, bom: bool) -> WincentResult<NamedTempFile> {
     let temp_script_file = Builder::new()
         .prefix("wincent_")
         .suffix(".ps1")
@@ -172,23 +335,203 @@ pub(crate) fn execute_ps_script(
         .tempfile()
         .map_err(WincentError::Io)?;
 
-    let bom = [0xEF, 0xBB, 0xBF];
     let mut file = temp_script_file.as_file();
-    file.write_all(&bom)?;
+    if bom {
+        file.write_all(&UTF8_BOM)?;
+    }
     file.write_all(content.as_bytes())?;
     file.flush()?;
 
-    Command::new("powershell")
-        .args([
-            "-ExecutionPolicy",
-            "Bypass",
-            "-File",
-            temp_script_file.into_temp_path().to_str().ok_or_else(|| {
-                WincentError::InvalidPath("Failed to convert temp file path".to_string())
-            })?,
-        ])
-        .output()
-        .map_err(|e| WincentError::PowerShellExecution(e.to_string()))
+    Ok(temp_script_file)
+}
+
+/// Encodes script content the way `-EncodedCommand` expects it, as Base64 of UTF-16LE.
+fn encode_command(content: &str) -> String {
+    let bytes: Vec<u8> = content.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let block = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                let index = (block >> (18 - 6 * i)) & 0x3F;
+                encoded.push(BASE64_ALPHABET[index as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+/// Encodes script content for `-EncodedCommand` if it should run inline.
+///
+/// Content whose encoded form exceeds [`MAX_ENCODED_COMMAND_LENGTH`], such as scripts for
+/// deeply nested paths, is never inlined since it would overflow the command line.
+fn inline_command(content: &str, inline: bool) -> Option<String> {
+    Some(content)
+        .filter(|_| inline)
+        .map(encode_command)
+        .filter(|encoded| encoded.len() <= MAX_ENCODED_COMMAND_LENGTH)
+}
+
+/// Executes arbitrary script content, reporting it to metrics sinks under `name`.
+///
+/// The content is passed inline when `inline` is set and the configuration allows it.
+pub(crate) fn execute_ps_content(
+    name: &str,
+    content: &str,
+    inline: bool,
+    timeout: Option<Duration>,
+) -> WincentResult<std::process::Output> {
+    let started = metrics::start();
+
+    let result = run_ps_content(content, inline, timeout);
+
+    let success = matches!(&result, Ok(output) if output.status.success());
+    metrics::finish(name, started, success);
+
+    result
+}
+
+/// Runs the generated script with PowerShell.
+///
+/// Parameter-less scripts are passed inline with `-EncodedCommand` unless disabled in
+/// the configuration, other scripts are written to a temporary file run with `-File`.
+fn run_ps_script(
+    method: Script,
+    para: Option<&str>,
+    timeout: Option<Duration>,
+) -> WincentResult<std::process::Output> {
+    let inline = method.is_parameterless();
+    let content = get_script_content(method, para)?;
+
+    run_ps_content(&content, inline, timeout)
+}
+
+/// Runs script content with PowerShell, inline if allowed and short enough.
+///
+/// Scripts that should go through a temporary file are passed inline as well when the
+/// file cannot be written, as long as they fit in a command line.
+fn run_ps_content(
+    content: &str,
+    inline: bool,
+    timeout: Option<Duration>,
+) -> WincentResult<std::process::Output> {
+    let config = config::get_config();
+
+    let encoded = inline_command(content, inline && config.encoded_commands);
+
+    let mut command = Command::new("powershell");
+    command.args(["-ExecutionPolicy", "Bypass"]);
+    if let Some(working_dir) = &config.script_working_dir {
+        command.current_dir(working_dir);
+    }
+    command.envs(config.script_env.iter().map(|(key, value)| (key, value)));
+    if config.hide_console_window {
+        command.creation_flags(CREATE_NO_WINDOW.0);
+    }
+
+    // The temporary file is deleted when dropped, so it is kept until PowerShell exits
+    let mut script_file = None;
+    match encoded {
+        Some(encoded) => {
+            command.args(["-EncodedCommand", &encoded]);
+        }
+        None => match write_script_file(content, config.script_bom) {
+            Ok(temp_file) => {
+                let script_path = script_file.insert(temp_file.into_temp_path());
+                command.args([
+                    "-File",
+                    script_path.to_str().ok_or_else(|| {
+                        WincentError::InvalidPath("Failed to convert temp file path".to_string())
+                    })?,
+                ]);
+            }
+            Err(err) => {
+                // Without write access to the temp folder the script can still run inline
+                let encoded = inline_command(content, true).ok_or_else(|| {
+                    WincentError::UnsupportedOperation(format!(
+                        "Script of {} characters is too long for the command line and the temporary script file could not be written: {}",
+                        content.chars().count(),
+                        err
+                    ))
+                })?;
+                log::warn!(
+                    "Failed to write temporary script file, passing it inline instead: {}",
+                    err
+                );
+                command.args(["-EncodedCommand", &encoded]);
+            }
+        },
+    }
+
+    match timeout {
+        None => command.output().map_err(map_spawn_error),
+        Some(timeout) => {
+            let child = command
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(map_spawn_error)?;
+            wait_with_timeout(child, timeout)
+        }
+    }
+}
+
+/// Waits for a child process and collects its output, killing it once `timeout` elapsed.
+fn wait_with_timeout(mut child: Child, timeout: Duration) -> WincentResult<std::process::Output> {
+    // Drain the pipes while waiting, a full pipe buffer would block the child forever
+    let stdout = child.stdout.take().map(read_in_background);
+    let stderr = child.stderr.take().map(read_in_background);
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(WincentError::Timeout(timeout));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    let collect = |reader: Option<std::thread::JoinHandle<std::io::Result<Vec<u8>>>>| {
+        reader.map_or(Ok(Vec::new()), |handle| {
+            handle.join().unwrap_or_else(|_| Ok(Vec::new()))
+        })
+    };
+
+    Ok(std::process::Output {
+        status,
+        stdout: collect(stdout)?,
+        stderr: collect(stderr)?,
+    })
+}
+
+/// Reads a pipe to its end on a separate thread.
+fn read_in_background<R: Read + Send + 'static>(
+    mut pipe: R,
+) -> std::thread::JoinHandle<std::io::Result<Vec<u8>>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        pipe.read_to_end(&mut buffer)?;
+        Ok(buffer)
+    })
+}
+
+/// Maps a failure to spawn PowerShell, distinguishing a missing executable.
+fn map_spawn_error(err: std::io::Error) -> WincentError {
+    match err.kind() {
+        std::io::ErrorKind::NotFound => WincentError::PowerShellNotFound,
+        _ => WincentError::PowerShellSpawn(err),
+    }
 }
 
 #[cfg(test)]
@@ -200,6 +543,7 @@ mod tests {
         let path = "C:\\Users\\User\\Documents";
         let script = get_script_content(Script::PinToFrequentFolder, Some(path)).unwrap();
         assert!(script.contains("pintohome"));
+        assert!(script.contains(&format!("exit {}", EXIT_ALREADY_EXISTS)));
     }
 
     #[test]
@@ -207,6 +551,7 @@ mod tests {
         let path = "C:\\Users\\User\\Documents";
         let script = get_script_content(Script::UnpinFromFrequentFolder, Some(path)).unwrap();
         assert!(script.contains("unpinfromhome"));
+        assert!(script.contains(&format!("exit {}", EXIT_NOT_FOUND)));
     }
 
     #[test]
@@ -214,12 +559,76 @@ mod tests {
         let path = "C:\\Users\\User\\Documents";
         let script = get_script_content(Script::RemoveRecentFile, Some(path)).unwrap();
         assert!(script.contains("remove"));
+        assert!(script.contains(&format!("exit {}", EXIT_NOT_FOUND)));
+    }
+
+    #[test]
+    fn test_quote_literal() {
+        assert_eq!(quote_literal("C:\\Users\\me"), "'C:\\Users\\me'");
+        assert_eq!(quote_literal("C:\\it's"), "'C:\\it''s'");
+        assert_eq!(
+            quote_literal("C:\\it\u{2019}s"),
+            "'C:\\it\u{2019}\u{2019}s'"
+        );
+        assert_eq!(quote_literal("$env:TEMP \"`"), "'$env:TEMP \"`'");
+    }
+
+    #[test]
+    fn test_mutation_scripts_quote_path() {
+        let path = "::{x}\"; Remove-Item -Recurse C:\\Users; \"'";
+        for script in [
+            Script::RemoveRecentFile,
+            Script::PinToFrequentFolder,
+            Script::UnpinFromFrequentFolder,
+        ] {
+            let content = get_script_content(script, Some(path)).unwrap();
+            assert!(content.contains(&format!("$path = {};", quote_literal(path))));
+            assert!(
+                !content.contains(&format!("\"{}\"", path)),
+                "The path should only appear as a single-quoted literal"
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_query_pinned_folder_script() {
+        let script = get_script_content(Script::QueryPinnedFolder, None).unwrap();
+        assert!(script.contains(FREQUENT_FOLDERS_NAMESPACE));
+        assert!(script.contains("System.Home.IsPinned"));
+    }
+
+    #[test]
+    fn test_namespace_constant_matches_scripts() {
+        use crate::shell::QUICK_ACCESS_NAMESPACE;
+
+        assert!(QUERY_FREQUENT_FOLDER.contains(FREQUENT_FOLDERS_NAMESPACE));
+        assert!(QUERY_RECENT_FILE.contains(QUICK_ACCESS_NAMESPACE));
+        assert!(QUERY_QUICK_ACCESS.contains(QUICK_ACCESS_NAMESPACE));
+    }
+
+    #[test]
+    fn test_get_query_recent_file_top_script() {
+        let script = get_script_content(Script::QueryRecentFileTop, Some("5")).unwrap();
+        assert!(script.contains("Select-Object -First 5 |"));
+        assert!(script.contains("IsFolder -eq $false"));
+
+        assert!(matches!(
+            get_script_content(Script::QueryRecentFileTop, Some("5; Remove-Item")),
+            Err(WincentError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            get_script_content(Script::QueryRecentFileTop, None),
+            Err(WincentError::MissingParameter)
+        ));
     }
 
     #[test]
     fn test_get_check_query_feasible_script() {
         let script = get_script_content(Script::CheckQueryFeasible, None).unwrap();
         assert!(script.contains("shell:::{679f85cb-0220-4080-b29b-5540cc05aab6}"));
+        assert!(script.contains(&format!("exit {}", EXIT_TIMED_OUT)));
+        assert!(script.contains(&format!("exit {}", EXIT_NAMESPACE_UNAVAILABLE)));
+        assert!(!script.contains("{timed_out}"));
     }
 
     #[test]
@@ -228,6 +637,120 @@ mod tests {
         assert!(script.contains("pintohome"));
     }
 
+    #[test]
+    fn test_write_script_file_encoding() -> WincentResult<()> {
+        let content =
+            get_script_content(Script::PinToFrequentFolder, Some("C:\\Users\\用户\\文档"))?;
+
+        for bom in [true, false] {
+            let file = write_script_file(&content, bom)?;
+            let bytes = std::fs::read(file.path())?;
+
+            let body = match bytes.strip_prefix(&UTF8_BOM) {
+                Some(body) => {
+                    assert!(bom, "BOM should not be written when disabled");
+                    body
+                }
+                None => {
+                    assert!(!bom, "BOM should be written when enabled");
+                    &bytes[..]
+                }
+            };
+            assert_eq!(
+                String::from_utf8(body.to_vec()).expect("Script should be valid UTF-8"),
+                content
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode_command() {
+        assert_eq!(encode_command(""), "");
+        assert_eq!(encode_command("a"), "YQA=");
+        assert_eq!(encode_command("dir"), "ZABpAHIA");
+        assert_eq!(encode_command("é"), "6QA=");
+    }
+
+    #[test]
+    fn test_parameterless_scripts_need_no_parameter() {
+        for script in [
+            Script::RefreshExplorer,
+            Script::QueryQuickAccess,
+            Script::QuertRecentFile,
+            Script::QueryFrequentFolder,
+            Script::QueryPinnedFolder,
+            Script::CheckQueryFeasible,
+            Script::CheckPinUnpinFeasible,
+        ] {
+            assert!(script.is_parameterless());
+            let content = get_script_content(script, None).unwrap();
+            assert!(encode_command(&content).len() <= MAX_ENCODED_COMMAND_LENGTH);
+        }
+
+        assert!(!Script::PinToFrequentFolder.is_parameterless());
+        assert!(!Script::QueryRecentFileTop.is_parameterless());
+    }
+
+    #[test]
+    #[ignore]
+    #[serial_test::serial]
+    fn test_script_working_dir_and_env() -> WincentResult<()> {
+        let initial = config::get_config();
+        let working_dir = tempfile::tempdir()?;
+
+        let mut custom = initial.clone();
+        custom.script_working_dir = Some(working_dir.path().to_path_buf());
+        custom.script_env = vec![("WINCENT_TEST_VAR".to_string(), "wincent".to_string())];
+        config::set_config(custom);
+
+        let output = run_ps_content(
+            "Write-Output $env:WINCENT_TEST_VAR; Write-Output (Get-Location).Path",
+            true,
+            None,
+        );
+        config::set_config(initial);
+
+        let stdout = String::from_utf8_lossy(&output?.stdout).to_string();
+        let mut lines = stdout.lines().map(str::trim);
+        assert_eq!(lines.next(), Some("wincent"));
+        assert!(crate::utils::paths_equal(
+            lines.next().unwrap_or_default(),
+            working_dir.path().to_str().unwrap()
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_command_respects_length_limit() {
+        assert!(inline_command("Write-Output 1", true).is_some());
+        assert!(inline_command("Write-Output 1", false).is_none());
+
+        let long_path = format!("\\\\?\\C:\\{}", "nested\\".repeat(3000));
+        let content = get_script_content(Script::PinToFrequentFolder, Some(&long_path)).unwrap();
+        assert!(
+            inline_command(&content, true).is_none(),
+            "Long scripts should go through a file"
+        );
+    }
+
+    #[test]
+    fn test_map_spawn_error() {
+        let not_found = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        assert!(matches!(
+            map_spawn_error(not_found),
+            WincentError::PowerShellNotFound
+        ));
+
+        let denied = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        assert!(matches!(
+            map_spawn_error(denied),
+            WincentError::PowerShellSpawn(_)
+        ));
+    }
+
     #[test]
     fn test_script_content_validity() {
         let path = "C:\\Users\\User\\Documents";
@@ -0,0 +1,61 @@
+//! Identifiers of the Shell namespaces backing Windows Quick Access.
+//!
+//! Useful for Explorer integrations working alongside wincent, for example to open
+//! Quick Access with `explorer.exe` or to browse it through `Shell.Application`.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use wincent::shell::QUICK_ACCESS_NAMESPACE;
+//!
+//! std::process::Command::new("explorer.exe")
+//!     .arg(QUICK_ACCESS_NAMESPACE)
+//!     .spawn()
+//!     .expect("Failed to open Quick Access");
+//! ```
+
+use windows::core::GUID;
+
+/// GUID of the Quick Access namespace, listing recent files and frequent folders.
+pub const QUICK_ACCESS_GUID: &str = "679f85cb-0220-4080-b29b-5540cc05aab6";
+
+/// GUID of the frequent folders namespace, both pinned and automatically added.
+///
+/// Windows has no separate namespace for pinned items, they are the items of this
+/// namespace with the `System.Home.IsPinned` property set.
+pub const FREQUENT_FOLDERS_GUID: &str = "3936E9E4-D92C-4EEE-A85A-BC16D5EA0819";
+
+/// [`QUICK_ACCESS_GUID`] as a parsed value.
+pub const QUICK_ACCESS: GUID = GUID::from_u128(0x679f85cb_0220_4080_b29b_5540cc05aab6);
+
+/// [`FREQUENT_FOLDERS_GUID`] as a parsed value.
+pub const FREQUENT_FOLDERS: GUID = GUID::from_u128(0x3936e9e4_d92c_4eee_a85a_bc16d5ea0819);
+
+/// Shell path of the Quick Access namespace, as accepted by `Shell.Namespace` and Explorer.
+pub const QUICK_ACCESS_NAMESPACE: &str = "shell:::{679f85cb-0220-4080-b29b-5540cc05aab6}";
+
+/// Shell path of the frequent folders namespace, as accepted by `Shell.Namespace` and Explorer.
+pub const FREQUENT_FOLDERS_NAMESPACE: &str = "shell:::{3936E9E4-D92C-4EEE-A85A-BC16D5EA0819}";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guid_constants_match() {
+        assert_eq!(GUID::from(QUICK_ACCESS_GUID), QUICK_ACCESS);
+        assert_eq!(GUID::from(FREQUENT_FOLDERS_GUID), FREQUENT_FOLDERS);
+    }
+
+    #[test]
+    fn test_namespace_constants_match() {
+        assert_eq!(
+            QUICK_ACCESS_NAMESPACE,
+            format!("shell:::{{{}}}", QUICK_ACCESS_GUID)
+        );
+        assert_eq!(
+            FREQUENT_FOLDERS_NAMESPACE,
+            format!("shell:::{{{}}}", FREQUENT_FOLDERS_GUID)
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Query information about the running Windows system.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use wincent::{system::windows_version, WincentResult};
+//!
+//! fn main() -> WincentResult<()> {
+//!     let version = windows_version()?;
+//!     println!(
+//!         "Windows {}.{} build {} (server: {}, Windows 11: {})",
+//!         version.major, version.minor, version.build, version.is_server, version.is_win11
+//!     );
+//!     Ok(())
+//! }
+//! ```
+
+use crate::{
+    feasible::{
+        check_feasible_within, current_execution_policy, ExecutionPolicy, FeasibilityReport,
+    },
+    query::get_items_timeout,
+    scripts::execute_ps_content,
+    utils::{get_os_version, is_admin},
+    QuickAccess, WincentResult,
+};
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// First build number of Windows 11.
+const WIN11_FIRST_BUILD: u32 = 22000;
+
+/// Version information of the running Windows system.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowsVersion {
+    /// Major version number.
+    pub major: u32,
+    /// Minor version number.
+    pub minor: u32,
+    /// Build number.
+    pub build: u32,
+    /// Whether this is a Server edition, where Quick Access behaves differently.
+    pub is_server: bool,
+    /// Whether the build number is Windows 11 or later (build 22000 and above).
+    pub is_win11: bool,
+}
+
+impl WindowsVersion {
+    /// Builds the version from raw `OSVERSIONINFOEXW` fields.
+    pub(crate) fn from_raw(major: u32, minor: u32, build: u32, product_type: u8) -> Self {
+        // 0x01 equals VER_NT_WORKSTATION, domain controllers and servers use other values
+        let is_server = product_type != 0x01;
+
+        WindowsVersion {
+            major,
+            minor,
+            build,
+            is_server,
+            is_win11: major == 10 && build >= WIN11_FIRST_BUILD,
+        }
+    }
+}
+
+/// Gets the version of the running Windows system, including build number and edition.
+///
+/// # Returns
+///
+/// Returns the major, minor and build numbers along with the edition flags.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{system::windows_version, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let version = windows_version()?;
+///     if version.is_server {
+///         println!("Quick Access may behave differently on Server editions");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn windows_version() -> WincentResult<WindowsVersion> {
+    let info = get_os_version()?;
+
+    Ok(WindowsVersion::from_raw(
+        info.dwMajorVersion,
+        info.dwMinorVersion,
+        info.dwBuildNumber,
+        info.wProductType,
+    ))
+}
+
+/// Time allowed to each probe run while collecting [`Diagnostics`].
+const DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(10);
+
+const QUERY_POWERSHELL_VERSION: &str =
+    "Write-Output \"$($PSVersionTable.PSVersion) ($($PSVersionTable.PSEdition))\"";
+
+/// Environment details relevant to Quick Access support, see [`diagnostics`].
+///
+/// Item counts are collected instead of paths, and the user profile folder is masked in
+/// paths, so the report can be shared publicly. Probes that failed are `None`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// Version of the wincent crate.
+    pub crate_version: &'static str,
+    /// Version of the running Windows system.
+    pub windows: Option<WindowsVersion>,
+    /// Whether the process runs with administrator rights.
+    pub is_admin: bool,
+    /// Version and edition reported by the `powershell` host wincent runs.
+    pub powershell: Option<String>,
+    /// PowerShell execution policy of the scope wincent checks.
+    pub execution_policy: Option<ExecutionPolicy>,
+    /// Results of the feasibility checks.
+    pub feasibility: Option<FeasibilityReport>,
+    /// Number of recent files.
+    pub recent_files: Option<usize>,
+    /// Number of frequent folders.
+    pub frequent_folders: Option<usize>,
+    /// Folder temporary script files are written to.
+    pub script_dir: String,
+}
+
+/// Formats an optional value, `unknown` if missing.
+fn or_unknown<T: fmt::Display>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map_or_else(|| "unknown".to_string(), ToString::to_string)
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "wincent: {}", self.crate_version)?;
+        match &self.windows {
+            Some(version) => writeln!(
+                f,
+                "Windows: {}.{}.{} (server: {}, Windows 11: {})",
+                version.major, version.minor, version.build, version.is_server, version.is_win11
+            )?,
+            None => writeln!(f, "Windows: unknown")?,
+        }
+        writeln!(f, "Administrator: {}", self.is_admin)?;
+        writeln!(f, "PowerShell: {}", or_unknown(&self.powershell))?;
+        writeln!(
+            f,
+            "Execution policy: {}",
+            or_unknown(&self.execution_policy)
+        )?;
+        match &self.feasibility {
+            Some(report) => writeln!(
+                f,
+                "Feasibility: script {}, query {}, pin/unpin {}",
+                report.script,
+                or_unknown(&report.query),
+                or_unknown(&report.pinunpin)
+            )?,
+            None => writeln!(f, "Feasibility: unknown")?,
+        }
+        writeln!(f, "Recent files: {}", or_unknown(&self.recent_files))?;
+        writeln!(
+            f,
+            "Frequent folders: {}",
+            or_unknown(&self.frequent_folders)
+        )?;
+        write!(f, "Script directory: {}", self.script_dir)
+    }
+}
+
+/// Replaces the user profile folder at the start of a path with `%USERPROFILE%`.
+pub(crate) fn redact_user_profile(path: &str, profile: Option<&str>) -> String {
+    let Some(profile) = profile
+        .map(|p| p.trim_end_matches('\\'))
+        .filter(|p| !p.is_empty())
+    else {
+        return path.to_string();
+    };
+
+    let starts_with_profile = path
+        .get(..profile.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(profile));
+    let at_boundary = matches!(path.as_bytes().get(profile.len()), None | Some(b'\\'));
+
+    if starts_with_profile && at_boundary {
+        format!("%USERPROFILE%{}", &path[profile.len()..])
+    } else {
+        path.to_string()
+    }
+}
+
+/// Gets the version of the `powershell` host.
+fn powershell_version() -> WincentResult<String> {
+    let output = execute_ps_content(
+        "QueryPowerShellVersion",
+        QUERY_POWERSHELL_VERSION,
+        true,
+        Some(DIAGNOSTICS_TIMEOUT),
+    )?;
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Collects a diagnostics report to attach to support requests.
+///
+/// Every probe is run even if others fail, a failed probe is reported as unknown. This
+/// runs several PowerShell scripts and may take a few seconds.
+///
+/// # Returns
+///
+/// Returns the report, its `Display` output is meant to be pasted into an issue.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::system::diagnostics;
+///
+/// println!("{}", diagnostics());
+/// ```
+pub fn diagnostics() -> Diagnostics {
+    let profile = std::env::var("USERPROFILE").ok();
+    let script_dir: PathBuf = std::env::temp_dir();
+    let count = |qa_type| {
+        get_items_timeout(qa_type, DIAGNOSTICS_TIMEOUT)
+            .ok()
+            .map(|items| items.len())
+    };
+
+    Diagnostics {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        windows: windows_version().ok(),
+        is_admin: is_admin(),
+        powershell: powershell_version().ok().filter(|v| !v.is_empty()),
+        execution_policy: current_execution_policy().ok(),
+        feasibility: check_feasible_within(DIAGNOSTICS_TIMEOUT).ok(),
+        recent_files: count(QuickAccess::RecentFiles),
+        frequent_folders: count(QuickAccess::FrequentFolders),
+        script_dir: redact_user_profile(&script_dir.to_string_lossy(), profile.as_deref()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_version_from_raw() {
+        let win10 = WindowsVersion::from_raw(10, 0, 19045, 0x01);
+        assert!(!win10.is_win11);
+        assert!(!win10.is_server);
+
+        let win11 = WindowsVersion::from_raw(10, 0, 22631, 0x01);
+        assert!(win11.is_win11);
+        assert!(!win11.is_server);
+
+        let server = WindowsVersion::from_raw(10, 0, 20348, 0x03);
+        assert!(server.is_server);
+        assert!(!server.is_win11);
+    }
+
+    #[test]
+    fn test_redact_user_profile() {
+        let profile = Some("C:\\Users\\alice");
+        assert_eq!(
+            redact_user_profile("C:\\Users\\Alice\\AppData\\Local\\Temp", profile),
+            "%USERPROFILE%\\AppData\\Local\\Temp"
+        );
+        assert_eq!(
+            redact_user_profile("C:\\Users\\alice2\\Temp", profile),
+            "C:\\Users\\alice2\\Temp"
+        );
+        assert_eq!(redact_user_profile("D:\\Temp", profile), "D:\\Temp");
+        assert_eq!(redact_user_profile("D:\\Temp", None), "D:\\Temp");
+    }
+
+    #[test]
+    fn test_diagnostics_display() {
+        let report = Diagnostics {
+            crate_version: "0.1.1",
+            windows: Some(WindowsVersion::from_raw(10, 0, 22631, 0x01)),
+            is_admin: false,
+            powershell: Some("5.1.22621.2506 (Desktop)".to_string()),
+            execution_policy: Some(ExecutionPolicy::RemoteSigned),
+            feasibility: Some(FeasibilityReport {
+                script: true,
+                query: Some(true),
+                pinunpin: None,
+            }),
+            recent_files: Some(12),
+            frequent_folders: None,
+            script_dir: "%USERPROFILE%\\AppData\\Local\\Temp".to_string(),
+        };
+
+        let text = report.to_string();
+        assert!(text.contains("Windows: 10.0.22631 (server: false, Windows 11: true)"));
+        assert!(text.contains("Execution policy: RemoteSigned"));
+        assert!(text.contains("Feasibility: script true, query true, pin/unpin unknown"));
+        assert!(text.contains("Recent files: 12"));
+        assert!(text.contains("Frequent folders: unknown"));
+    }
+
+    #[test]
+    fn test_windows_version() -> WincentResult<()> {
+        let version = windows_version()?;
+        assert!(
+            version.major >= 6,
+            "Should report a supported Windows version"
+        );
+        Ok(())
+    }
+}
@@ -28,6 +28,50 @@ pub(crate) fn create_test_file(dir: &Path, name: &str, content: &str) -> Wincent
     Ok(file_path)
 }
 
+/// Create a `.lnk` shortcut pointing to a target
+pub(crate) fn create_test_shortcut(
+    dir: &Path,
+    name: &str,
+    target: &Path,
+) -> WincentResult<PathBuf> {
+    create_test_shortcut_with_arguments(dir, name, target, None)
+}
+
+/// Create a `.lnk` shortcut pointing to a target, started with arguments in `dir`
+pub(crate) fn create_test_shortcut_with_arguments(
+    dir: &Path,
+    name: &str,
+    target: &Path,
+    arguments: Option<&str>,
+) -> WincentResult<PathBuf> {
+    use windows::core::{Interface, HSTRING};
+    use windows::Win32::System::Com::{
+        CoCreateInstance, CoInitializeEx, CoUninitialize, IPersistFile, CLSCTX_INPROC_SERVER,
+        COINIT_APARTMENTTHREADED,
+    };
+    use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+
+    let lnk_path = dir.join(name);
+
+    unsafe {
+        let _ = CoInitializeEx(None, COINIT_APARTMENTTHREADED);
+        let result = (|| -> windows::core::Result<()> {
+            let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+            link.SetPath(&HSTRING::from(target.as_os_str()))?;
+            if let Some(arguments) = arguments {
+                link.SetArguments(&HSTRING::from(arguments))?;
+                link.SetWorkingDirectory(&HSTRING::from(dir.as_os_str()))?;
+            }
+            let persist_file: IPersistFile = link.cast()?;
+            persist_file.Save(&HSTRING::from(lnk_path.as_os_str()), true)
+        })();
+        CoUninitialize();
+        result?;
+    }
+
+    Ok(lnk_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
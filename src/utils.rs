@@ -1,18 +1,286 @@
+//! Helpers for working with Windows shell items.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use wincent::{utils::resolve_shortcut, error::WincentError};
+//!
+//! fn main() -> Result<(), WincentError> {
+//!     let target = resolve_shortcut("C:\\Users\\me\\Desktop\\Project.lnk")?;
+//!     println!("Shortcut points to {}", target);
+//!     Ok(())
+//! }
+//! ```
+
 #![allow(dead_code)]
 
 use crate::{
     error::WincentError,
+    handle::{validate_path, PathType},
     scripts::{execute_ps_script, Script},
-    WincentResult,
+    QuickAccess, WincentResult,
+};
+use std::ffi::OsString;
+use std::io::Read;
+use std::os::windows::ffi::OsStringExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::SystemTime;
+use windows::core::HRESULT;
+use windows::core::{s, w, Interface, GUID, HSTRING};
+use windows::Win32::Foundation::{BOOL, HANDLE, NTSTATUS, RPC_E_CHANGED_MODE};
+use windows::Win32::Storage::FileSystem::{
+    GetFileInformationByHandle, BY_HANDLE_FILE_INFORMATION, FILE_FLAG_BACKUP_SEMANTICS,
+};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoGetApartmentType, CoInitializeEx, CoTaskMemFree, CoUninitialize,
+    IPersistFile, APTTYPE, APTTYPEQUALIFIER, APTTYPEQUALIFIER_IMPLICIT_MTA, CLSCTX_INPROC_SERVER,
+    COINIT_APARTMENTTHREADED, STGM_READ,
+};
+use windows::Win32::System::LibraryLoader::{GetModuleHandleW, GetProcAddress};
+use windows::Win32::System::SystemInformation::OSVERSIONINFOEXW;
+use windows::Win32::UI::Shell::{
+    FOLDERID_Recent, IShellLinkW, IsUserAnAdmin, SHGetKnownFolderPath, ShellLink, KNOWN_FOLDER_FLAG,
 };
-use windows::Win32::Foundation::BOOL;
-use windows::Win32::UI::Shell::IsUserAnAdmin;
+
+/// Jump list file backing the Quick Access recent files list.
+const RECENT_FILES_JUMPLIST: &str = "5f7b5f1e01b83767.automaticDestinations-ms";
+
+/// Jump list file backing the Quick Access frequent folders list.
+const FREQUENT_FOLDERS_JUMPLIST: &str = "f01b4d95cf55d32a.automaticDestinations-ms";
+
+/// Largest jump list file read, real ones stay well below a megabyte.
+pub(crate) const MAX_JUMPLIST_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Size of an OLE compound document header, the smallest possible jump list file.
+const MIN_JUMPLIST_SIZE: usize = 512;
+
+static MUTATION_LOCK: Mutex<()> = Mutex::new(());
+static STATE_VERSION: AtomicU64 = AtomicU64::new(0);
+
+/// Holds the mutation lock, bumping the state version once the change is done.
+pub(crate) struct MutationGuard {
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Drop for MutationGuard {
+    fn drop(&mut self) {
+        // Runs before the lock is released, so the new version is visible with the change
+        STATE_VERSION.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+/// Serializes changes to Quick Access across the process, held for one change at a time.
+///
+/// Only taken by the innermost operations, so composite operations can call each other
+/// without deadlocking. Queries are never blocked.
+pub(crate) fn lock_mutations() -> MutationGuard {
+    let lock = match MUTATION_LOCK.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+
+    MutationGuard { _lock: lock }
+}
+
+/// Returns the number of Quick Access changes attempted by this process so far.
+pub(crate) fn state_version() -> u64 {
+    STATE_VERSION.load(Ordering::SeqCst)
+}
+
+/// Keeps COM initialized on the current thread, uninitializing it on drop when needed.
+pub(crate) struct ComGuard {
+    uninitialize: bool,
+}
+
+impl ComGuard {
+    /// Initializes COM in a single-threaded apartment on the current thread.
+    ///
+    /// If the host already initialized COM in a different apartment, COM is usable
+    /// as is and the guard leaves it alone.
+    pub(crate) fn init() -> WincentResult<ComGuard> {
+        // Leave COM owned by the host alone, so repeated calls do not churn its apartment
+        if com_initialized_by_host() {
+            return Ok(ComGuard {
+                uninitialize: false,
+            });
+        }
+
+        let hr = unsafe { CoInitializeEx(Some(std::ptr::null_mut()), COINIT_APARTMENTTHREADED) };
+
+        com_init_needs_uninitialize(hr)
+            .map(|uninitialize| ComGuard { uninitialize })
+            .map_err(WincentError::WindowsApi)
+    }
+}
+
+impl Drop for ComGuard {
+    fn drop(&mut self) {
+        if self.uninitialize {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
+
+/// Checks whether COM is already initialized on the current thread.
+///
+/// A thread only running in the implicit multithreaded apartment of the process did
+/// not initialize COM itself and is reported as not initialized.
+pub(crate) fn com_initialized_by_host() -> bool {
+    let mut apt_type = APTTYPE::default();
+    let mut qualifier = APTTYPEQUALIFIER::default();
+
+    let result = unsafe { CoGetApartmentType(&mut apt_type, &mut qualifier) };
+    result.is_ok() && qualifier != APTTYPEQUALIFIER_IMPLICIT_MTA
+}
+
+/// Decides whether a `CoInitializeEx` result has to be balanced with `CoUninitialize`.
+///
+/// `S_OK` and `S_FALSE` (already initialized in the same apartment) both take a
+/// reference that must be released. `RPC_E_CHANGED_MODE` means COM is already usable
+/// in another apartment and nothing was taken. Other results are errors.
+pub(crate) fn com_init_needs_uninitialize(hr: HRESULT) -> Result<bool, i32> {
+    if hr == RPC_E_CHANGED_MODE {
+        Ok(false)
+    } else if hr.is_ok() {
+        Ok(true)
+    } else {
+        Err(hr.0)
+    }
+}
 
 /// Checks if the current user has administrative privileges.
 pub(crate) fn is_admin() -> bool {
     unsafe { IsUserAnAdmin() == BOOL(1) }
 }
 
+/// Gets the operating system version information via `RtlGetVersion`.
+///
+/// Unlike `GetVersionExW`, `RtlGetVersion` reports the real version regardless of
+/// the application manifest.
+pub(crate) fn get_os_version() -> WincentResult<OSVERSIONINFOEXW> {
+    type RtlGetVersionFn = unsafe extern "system" fn(*mut OSVERSIONINFOEXW) -> NTSTATUS;
+
+    unsafe {
+        let ntdll = GetModuleHandleW(w!("ntdll.dll"))?;
+        let proc = GetProcAddress(ntdll, s!("RtlGetVersion")).ok_or_else(|| {
+            WincentError::SystemError("RtlGetVersion not found in ntdll.dll".to_string())
+        })?;
+        let rtl_get_version: RtlGetVersionFn = std::mem::transmute(proc);
+
+        let mut info = OSVERSIONINFOEXW {
+            dwOSVersionInfoSize: std::mem::size_of::<OSVERSIONINFOEXW>() as u32,
+            ..Default::default()
+        };
+
+        let status = rtl_get_version(&mut info);
+        if status.is_err() {
+            return Err(WincentError::WindowsApi(status.0));
+        }
+
+        Ok(info)
+    }
+}
+
+/// Normalizes a path for comparison and scripts, using backslashes as separators and
+/// removing trailing separators, keeping drive roots intact.
+pub(crate) fn normalize_path(path: &str) -> String {
+    let path = path.replace('/', "\\");
+    let trimmed = path.trim_end_matches('\\');
+
+    if trimmed.len() == 2 && trimmed.ends_with(':') {
+        format!("{}\\", trimmed)
+    } else if trimmed.is_empty() {
+        path
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Checks if two paths refer to the same location, ignoring case and trailing separators.
+pub(crate) fn paths_equal(a: &str, b: &str) -> bool {
+    normalize_path(a).to_lowercase() == normalize_path(b).to_lowercase()
+}
+
+/// Gets the volume serial number and file index identifying a file or directory.
+///
+/// Returns `None` if the path does not exist.
+pub(crate) fn file_id(path: &str) -> WincentResult<Option<(u32, u64)>> {
+    use std::os::windows::fs::OpenOptionsExt;
+    use std::os::windows::io::AsRawHandle;
+
+    // No access rights are needed to query the file information, and the backup
+    // semantics flag allows opening directories
+    let file = match std::fs::OpenOptions::new()
+        .access_mode(0)
+        .custom_flags(FILE_FLAG_BACKUP_SEMANTICS.0)
+        .open(path)
+    {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(WincentError::Io(e)),
+    };
+
+    let mut info = BY_HANDLE_FILE_INFORMATION::default();
+    unsafe { GetFileInformationByHandle(HANDLE(file.as_raw_handle()), &mut info) }?;
+
+    let index = (u64::from(info.nFileIndexHigh) << 32) | u64::from(info.nFileIndexLow);
+    Ok(Some((info.dwVolumeSerialNumber, index)))
+}
+
+/// Checks if two paths refer to the same file or directory on disk.
+///
+/// Paths are compared by the volume and file index Windows assigns to each file, so
+/// differently cased or written paths, hard links and paths through junctions to the
+/// same file compare equal.
+///
+/// # Arguments
+///
+/// * `a` - The first path
+/// * `b` - The second path
+///
+/// # Returns
+///
+/// Returns `true` if both paths exist and name the same file, `false` if they name
+/// different files or either does not exist.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{utils::same_file, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     if same_file("C:\\Docs\\report.docx", "c:/docs/REPORT.docx")? {
+///         println!("Same file");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn same_file(a: &str, b: &str) -> WincentResult<bool> {
+    let Some(a_id) = file_id(a)? else {
+        return Ok(false);
+    };
+
+    Ok(file_id(b)? == Some(a_id))
+}
+
+/// Checks if a path is the root itself or located below it, matching whole path components.
+pub(crate) fn is_path_under(path: &str, root: &str) -> bool {
+    let path = normalize_path(path).to_lowercase();
+    let root = normalize_path(root).to_lowercase();
+
+    if path == root {
+        return true;
+    }
+
+    if root.ends_with('\\') {
+        path.starts_with(&root)
+    } else {
+        path.starts_with(&format!("{}\\", root))
+    }
+}
+
 /// Refreshes the Windows Explorer window using a PowerShell script.
 pub(crate) fn refresh_explorer_window() -> WincentResult<()> {
     let output = execute_ps_script(Script::RefreshExplorer, None)?;
@@ -25,10 +293,407 @@ pub(crate) fn refresh_explorer_window() -> WincentResult<()> {
     }
 }
 
+/// Gets the path of the current user's Windows Recent folder.
+pub(crate) fn get_windows_recent_folder() -> WincentResult<String> {
+    get_known_folder_path(&FOLDERID_Recent)
+}
+
+/// Gets the path of a known folder of the current user.
+pub(crate) fn get_known_folder_path(folder_id: &GUID) -> WincentResult<String> {
+    get_known_folder_path_with_token(folder_id, HANDLE(std::ptr::null_mut()))
+}
+
+/// Gets the path of a known folder of the user an access token belongs to.
+///
+/// A null token stands for the current user.
+pub(crate) fn get_known_folder_path_with_token(
+    folder_id: &GUID,
+    token: HANDLE,
+) -> WincentResult<String> {
+    let result = unsafe { SHGetKnownFolderPath(folder_id, KNOWN_FOLDER_FLAG(0x00), token) }?;
+
+    unsafe {
+        let wide_str = OsString::from_wide(result.as_wide());
+        CoTaskMemFree(Some(result.as_ptr() as _));
+        wide_str
+            .into_string()
+            .map_err(|_| WincentError::SystemError("Invalid UTF-16".to_string()))
+    }
+}
+
+/// Resolves the target path of a `.lnk` shortcut file.
+///
+/// COM must be initialized on the calling thread. Shortcuts to virtual shell items
+/// have no filesystem target and resolve to an empty string.
+pub(crate) fn resolve_shortcut_with_api(lnk_path: &str) -> WincentResult<String> {
+    unsafe {
+        let link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)?;
+        let persist_file: IPersistFile = link.cast()?;
+        persist_file.Load(&HSTRING::from(lnk_path), STGM_READ)?;
+
+        // Buffer sized for extended-length paths
+        let mut target = vec![0u16; 32768];
+        link.GetPath(&mut target, std::ptr::null_mut(), 0)?;
+
+        let len = target.iter().position(|&c| c == 0).unwrap_or(target.len());
+        Ok(String::from_utf16_lossy(&target[..len]))
+    }
+}
+
+/// Resolves the target path of a `.lnk` shortcut file.
+///
+/// Only the target is returned, arguments and working directory stored in the shortcut
+/// are ignored. Shortcuts to virtual shell items, such as Control Panel entries, have no
+/// filesystem target and resolve to an empty string.
+///
+/// # Arguments
+///
+/// * `lnk_path` - The full path to the `.lnk` file
+///
+/// # Returns
+///
+/// Returns the path the shortcut points to.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{utils::resolve_shortcut, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let target = resolve_shortcut("C:\\Users\\me\\Desktop\\Project.lnk")?;
+///     println!("Shortcut points to {}", target);
+///     Ok(())
+/// }
+/// ```
+pub fn resolve_shortcut(lnk_path: &str) -> WincentResult<String> {
+    if lnk_path.is_empty() {
+        return Err(WincentError::InvalidPath("Empty path provided".to_string()));
+    }
+
+    if !Path::new(lnk_path).is_file() {
+        return Err(WincentError::InvalidPath(format!(
+            "Shortcut file does not exist: {}",
+            lnk_path
+        )));
+    }
+
+    let _com = ComGuard::init()?;
+    resolve_shortcut_with_api(lnk_path)
+}
+
+/// Validates a batch of paths, checking each exists and has the expected type.
+///
+/// Only the filesystem is checked, each path the same way as when it is added to Quick
+/// Access, so invalid paths can be filtered out before a bulk add.
+///
+/// # Arguments
+///
+/// * `paths` - The paths to check, each with the type it should have
+///
+/// # Returns
+///
+/// Returns one result per input, in the same order. Invalid paths fail with
+/// [`WincentError::InvalidPath`].
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{handle::PathType, utils::validate_paths};
+///
+/// let candidates = [
+///     ("C:\\Projects", PathType::Directory),
+///     ("C:\\Documents\\report.docx", PathType::File),
+/// ];
+/// let valid: Vec<&str> = candidates
+///     .iter()
+///     .zip(validate_paths(&candidates))
+///     .filter(|(_, result)| result.is_ok())
+///     .map(|((path, _), _)| *path)
+///     .collect();
+/// println!("{} valid paths", valid.len());
+/// ```
+pub fn validate_paths(paths: &[(&str, PathType)]) -> Vec<WincentResult<()>> {
+    paths
+        .iter()
+        .map(|(path, path_type)| validate_path(path, *path_type))
+        .collect()
+}
+
+/// Gets the jump list data files backing the given Quick Access category.
+pub(crate) fn get_quick_access_data_files(qa_type: QuickAccess) -> WincentResult<Vec<PathBuf>> {
+    let destinations = PathBuf::from(get_windows_recent_folder()?).join("AutomaticDestinations");
+
+    let files = match qa_type {
+        QuickAccess::RecentFiles => vec![destinations.join(RECENT_FILES_JUMPLIST)],
+        QuickAccess::FrequentFolders => vec![destinations.join(FREQUENT_FOLDERS_JUMPLIST)],
+        QuickAccess::All => vec![
+            destinations.join(RECENT_FILES_JUMPLIST),
+            destinations.join(FREQUENT_FOLDERS_JUMPLIST),
+        ],
+    };
+
+    Ok(files)
+}
+
+/// Reads a jump list data file, refusing files larger than `max_size` bytes.
+///
+/// Missing, empty and truncated files (shorter than a compound document header) are
+/// read as empty, Windows recreates them on the next access.
+pub(crate) fn read_jumplist_data(path: &Path, max_size: u64) -> WincentResult<Vec<u8>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(WincentError::Io(e)),
+    };
+
+    // Reading one byte past the limit also catches files growing while being read
+    let mut data = Vec::new();
+    file.take(max_size.saturating_add(1))
+        .read_to_end(&mut data)?;
+
+    if data.len() as u64 > max_size {
+        return Err(WincentError::SystemError(format!(
+            "Jump list file exceeds {} bytes: {}",
+            max_size,
+            path.display()
+        )));
+    }
+
+    if data.len() < MIN_JUMPLIST_SIZE {
+        data.clear();
+    }
+
+    Ok(data)
+}
+
+/// Gets the modification time of a jump list data file.
+///
+/// Returns `None` for files [`read_jumplist_data`] reads as empty, that is missing, empty
+/// and truncated ones, without reading their content.
+pub(crate) fn get_jumplist_modified(path: &Path) -> WincentResult<Option<SystemTime>> {
+    match std::fs::metadata(path) {
+        Ok(metadata) if metadata.len() < MIN_JUMPLIST_SIZE as u64 => Ok(None),
+        Ok(metadata) => Ok(Some(metadata.modified()?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(WincentError::Io(e)),
+    }
+}
+
 #[cfg(test)]
 mod utils_test {
     use super::*;
 
+    #[test]
+    fn test_read_jumplist_data_truncated() -> WincentResult<()> {
+        let dir = tempfile::tempdir()?;
+
+        let empty = dir.path().join("empty.automaticDestinations-ms");
+        std::fs::write(&empty, [])?;
+        assert!(read_jumplist_data(&empty, MAX_JUMPLIST_SIZE)?.is_empty());
+
+        let truncated = dir.path().join("truncated.automaticDestinations-ms");
+        std::fs::write(&truncated, [0xD0, 0xCF, 0x11, 0xE0])?;
+        assert!(
+            read_jumplist_data(&truncated, MAX_JUMPLIST_SIZE)?.is_empty(),
+            "Truncated file should be read as empty"
+        );
+
+        let missing = dir.path().join("missing.automaticDestinations-ms");
+        assert!(read_jumplist_data(&missing, MAX_JUMPLIST_SIZE)?.is_empty());
+
+        let complete = dir.path().join("complete.automaticDestinations-ms");
+        std::fs::write(&complete, vec![0u8; MIN_JUMPLIST_SIZE])?;
+        assert_eq!(
+            read_jumplist_data(&complete, MAX_JUMPLIST_SIZE)?.len(),
+            MIN_JUMPLIST_SIZE
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_jumplist_modified_truncated() -> WincentResult<()> {
+        let dir = tempfile::tempdir()?;
+
+        let truncated = dir.path().join("truncated.automaticDestinations-ms");
+        std::fs::write(&truncated, vec![0u8; MIN_JUMPLIST_SIZE - 1])?;
+        assert_eq!(get_jumplist_modified(&truncated)?, None);
+
+        let missing = dir.path().join("missing.automaticDestinations-ms");
+        assert_eq!(get_jumplist_modified(&missing)?, None);
+
+        let complete = dir.path().join("complete.automaticDestinations-ms");
+        std::fs::write(&complete, vec![0u8; MIN_JUMPLIST_SIZE])?;
+        assert!(get_jumplist_modified(&complete)?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_jumplist_data_oversized() -> WincentResult<()> {
+        let dir = tempfile::tempdir()?;
+        let oversized = dir.path().join("oversized.automaticDestinations-ms");
+        std::fs::write(&oversized, vec![0u8; 4096])?;
+
+        match read_jumplist_data(&oversized, 1024) {
+            Err(WincentError::SystemError(msg)) => {
+                assert!(
+                    msg.contains("exceeds 1024 bytes"),
+                    "Unexpected message: {}",
+                    msg
+                )
+            }
+            other => panic!("Oversized file should be refused: {:?}", other),
+        }
+
+        assert_eq!(read_jumplist_data(&oversized, 4096)?.len(), 4096);
+        Ok(())
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_resolve_shortcut() -> WincentResult<()> {
+        use crate::test_utils::{
+            cleanup_test_env, create_test_file, create_test_shortcut_with_arguments, setup_test_env,
+        };
+
+        let test_dir = setup_test_env()?;
+        let target = create_test_file(&test_dir, "target.txt", "content")?;
+        let lnk_path = create_test_shortcut_with_arguments(
+            &test_dir,
+            "target.lnk",
+            &target,
+            Some("--flag value"),
+        )?;
+
+        let resolved = resolve_shortcut(lnk_path.to_str().unwrap())?;
+        assert_eq!(
+            resolved,
+            target.to_str().unwrap(),
+            "Arguments should be ignored"
+        );
+
+        assert!(resolve_shortcut("").is_err());
+        assert!(resolve_shortcut(test_dir.join("missing.lnk").to_str().unwrap()).is_err());
+
+        cleanup_test_env(&test_dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_mutation_guard_bumps_state_version() {
+        let before = state_version();
+        {
+            let _guard = lock_mutations();
+        }
+        assert!(
+            state_version() > before,
+            "Releasing the mutation lock should bump the version"
+        );
+    }
+
+    #[test]
+    fn test_com_init_needs_uninitialize() {
+        use windows::Win32::Foundation::{E_OUTOFMEMORY, S_FALSE, S_OK};
+
+        assert_eq!(com_init_needs_uninitialize(S_OK), Ok(true));
+        assert_eq!(
+            com_init_needs_uninitialize(S_FALSE),
+            Ok(true),
+            "Repeated initialization should still be balanced"
+        );
+        assert_eq!(
+            com_init_needs_uninitialize(RPC_E_CHANGED_MODE),
+            Ok(false),
+            "Other apartment should be used as is"
+        );
+        assert_eq!(
+            com_init_needs_uninitialize(E_OUTOFMEMORY),
+            Err(E_OUTOFMEMORY.0)
+        );
+    }
+
+    #[test]
+    fn test_com_guard_in_multithreaded_apartment() -> WincentResult<()> {
+        use windows::Win32::System::Com::COINIT_MULTITHREADED;
+
+        std::thread::spawn(|| {
+            let hr = unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) };
+            assert!(hr.is_ok());
+
+            let guard = ComGuard::init();
+            assert!(guard.is_ok(), "Should reuse the existing apartment");
+            drop(guard);
+
+            unsafe { CoUninitialize() };
+        })
+        .join()
+        .expect("Thread should not panic");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_com_guard_in_host_apartment() {
+        std::thread::spawn(|| {
+            assert!(!com_initialized_by_host(), "Fresh thread has no COM");
+            let guard = ComGuard::init().expect("Should initialize COM");
+            assert!(
+                guard.uninitialize,
+                "COM initialized by the guard is owned by it"
+            );
+            drop(guard);
+            assert!(
+                !com_initialized_by_host(),
+                "Guard should uninitialize its COM"
+            );
+
+            let hr = unsafe { CoInitializeEx(None, COINIT_APARTMENTTHREADED) };
+            assert!(hr.is_ok());
+
+            for _ in 0..10 {
+                let guard = ComGuard::init().expect("Should reuse the host apartment");
+                assert!(!guard.uninitialize, "Host COM should not be managed");
+            }
+            assert!(
+                com_initialized_by_host(),
+                "Host COM should survive repeated guards"
+            );
+
+            unsafe { CoUninitialize() };
+        })
+        .join()
+        .expect("Thread should not panic");
+    }
+
+    #[test]
+    fn test_lock_mutations_is_exclusive() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let active = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let active = Arc::clone(&active);
+                std::thread::spawn(move || {
+                    for _ in 0..50 {
+                        let _guard = lock_mutations();
+                        assert_eq!(
+                            active.fetch_add(1, Ordering::SeqCst),
+                            0,
+                            "Mutations should not overlap"
+                        );
+                        std::thread::yield_now();
+                        active.fetch_sub(1, Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("Thread should not panic");
+        }
+    }
+
     #[test]
     fn test_check_admin() {
         let is_admin = is_admin();
@@ -39,4 +704,138 @@ mod utils_test {
     fn test_refresh_explorer() -> WincentResult<()> {
         refresh_explorer_window()
     }
+
+    #[test]
+    fn test_validate_paths() -> WincentResult<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("valid.txt");
+        std::fs::write(&file, "content")?;
+        let missing = dir.path().join("missing.txt");
+
+        let dir_path = dir.path().to_str().unwrap();
+        let file_path = file.to_str().unwrap();
+        let results = validate_paths(&[
+            (file_path, PathType::File),
+            (dir_path, PathType::Directory),
+            (missing.to_str().unwrap(), PathType::File),
+            (file_path, PathType::Directory),
+            (dir_path, PathType::File),
+            ("", PathType::File),
+        ]);
+
+        assert_eq!(results.len(), 6);
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        for result in &results[2..] {
+            assert!(
+                matches!(result, Err(WincentError::InvalidPath(_))),
+                "Unexpected result: {:?}",
+                result
+            );
+        }
+        assert!(validate_paths(&[]).is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_file() -> WincentResult<()> {
+        let dir = tempfile::tempdir()?;
+        let file = dir.path().join("original.txt");
+        std::fs::write(&file, "content")?;
+        let link = dir.path().join("hardlink.txt");
+        std::fs::hard_link(&file, &link)?;
+        let other = dir.path().join("other.txt");
+        std::fs::write(&other, "content")?;
+
+        let file_path = file.to_str().unwrap();
+        assert!(same_file(file_path, link.to_str().unwrap())?);
+        assert!(same_file(file_path, &file_path.to_uppercase())?);
+        assert!(!same_file(file_path, other.to_str().unwrap())?);
+
+        let dir_path = dir.path().to_str().unwrap();
+        assert!(same_file(dir_path, &format!("{}\\", dir_path))?);
+
+        let missing = dir.path().join("missing.txt");
+        assert!(!same_file(missing.to_str().unwrap(), file_path)?);
+        assert!(!same_file(file_path, missing.to_str().unwrap())?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("C:\\Projects\\"), "C:\\Projects");
+        assert_eq!(normalize_path("C:\\Projects"), "C:\\Projects");
+        assert_eq!(normalize_path("C:\\"), "C:\\");
+        assert_eq!(normalize_path("C:"), "C:\\");
+        assert_eq!(normalize_path("\\\\server\\share\\"), "\\\\server\\share");
+    }
+
+    #[test]
+    fn test_normalize_path_forward_slashes() {
+        assert_eq!(
+            normalize_path("C:/Users/me/file.txt"),
+            "C:\\Users\\me\\file.txt"
+        );
+        assert_eq!(normalize_path("C:/Projects/"), "C:\\Projects");
+        assert_eq!(normalize_path("C:/"), "C:\\");
+        assert_eq!(normalize_path("//server/share/"), "\\\\server\\share");
+        assert!(paths_equal("C:/Projects/App", "c:\\projects\\app\\"));
+        assert!(is_path_under("C:/Projects/App/main.rs", "C:\\Projects"));
+    }
+
+    #[test]
+    fn test_paths_equal() {
+        assert!(paths_equal("C:\\Projects\\", "c:\\projects"));
+        assert!(!paths_equal("C:\\Projects", "C:\\Projects2"));
+    }
+
+    #[test]
+    fn test_is_path_under() {
+        assert!(is_path_under("C:\\Projects\\app\\main.rs", "C:\\Projects"));
+        assert!(is_path_under("C:\\Projects\\app", "c:\\projects\\"));
+        assert!(is_path_under("C:\\Projects", "C:\\Projects"));
+        assert!(is_path_under("D:\\data\\file.txt", "D:\\"));
+        assert!(is_path_under(
+            "\\\\server\\share\\doc.txt",
+            "\\\\server\\share"
+        ));
+
+        // Sibling sharing the same prefix must not match
+        assert!(!is_path_under("C:\\Projects2\\app", "C:\\Projects"));
+        assert!(!is_path_under("C:\\Projects2", "C:\\Projects\\"));
+        assert!(!is_path_under("C:\\Other", "C:\\Projects"));
+        assert!(!is_path_under(
+            "\\\\server\\share2\\doc.txt",
+            "\\\\server\\share"
+        ));
+    }
+
+    #[test]
+    fn test_get_os_version() -> WincentResult<()> {
+        let info = get_os_version()?;
+        assert!(
+            info.dwMajorVersion >= 6,
+            "Should report a supported Windows version"
+        );
+        assert!(info.dwBuildNumber > 0, "Build number should be set");
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_quick_access_data_files() -> WincentResult<()> {
+        let recent = get_quick_access_data_files(QuickAccess::RecentFiles)?;
+        assert_eq!(recent.len(), 1);
+        assert!(recent[0].ends_with(RECENT_FILES_JUMPLIST));
+
+        let frequent = get_quick_access_data_files(QuickAccess::FrequentFolders)?;
+        assert_eq!(frequent.len(), 1);
+        assert!(frequent[0].ends_with(FREQUENT_FOLDERS_JUMPLIST));
+
+        let all = get_quick_access_data_files(QuickAccess::All)?;
+        assert_eq!(all, [recent, frequent].concat());
+
+        Ok(())
+    }
 }
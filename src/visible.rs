@@ -76,7 +76,12 @@
 //! }
 //! ```
 
-use crate::{error::WincentError, QuickAccess, WincentResult};
+use crate::{
+    error::WincentError,
+    feasible::is_recent_tracking_enabled,
+    utils::{lock_mutations, refresh_explorer_window},
+    QuickAccess, WincentResult,
+};
 
 /// Retrieves the registry key for Quick Access settings.
 fn get_quick_access_reg() -> WincentResult<winreg::RegKey> {
@@ -86,7 +91,17 @@ fn get_quick_access_reg() -> WincentResult<winreg::RegKey> {
     let hklm = RegKey::predef(HKEY_CURRENT_USER);
     hklm.create_subkey("SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Explorer")
         .map(|(key, _)| key)
-        .map_err(WincentError::Io)
+        .map_err(map_registry_write_error)
+}
+
+/// Maps a failed registry write, reporting a policy-locked key distinctly.
+pub(crate) fn map_registry_write_error(err: std::io::Error) -> WincentError {
+    match err.kind() {
+        std::io::ErrorKind::PermissionDenied => WincentError::UnsupportedOperation(
+            "Quick Access visibility is controlled by policy".to_string(),
+        ),
+        _ => WincentError::Io(err),
+    }
 }
 
 /// Checks and fixes the Quick Access registry settings.
@@ -99,11 +114,12 @@ fn check_fix_quick_acess_reg() -> WincentResult<()> {
             Ok(_) => {
                 // do nothing
             }
-            Err(_) => {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
                 reg_key
                     .set_value(value_name, &u32::from(1_u8))
-                    .map_err(WincentError::Io)?;
+                    .map_err(map_registry_write_error)?;
             }
+            Err(e) => return Err(WincentError::Io(e)),
         }
     }
 
@@ -137,9 +153,10 @@ pub(crate) fn set_visiable_with_registry(
         crate::QuickAccess::All => "ShowRecent",
     };
 
+    let _guard = lock_mutations();
     reg_key
         .set_value(reg_value, &u32::from(visiable))
-        .map_err(WincentError::Io)?;
+        .map_err(map_registry_write_error)?;
 
     Ok(())
 }
@@ -248,6 +265,279 @@ pub fn set_frequent_folders_visiable(is_visiable: bool) -> WincentResult<()> {
     set_visiable_with_registry(QuickAccess::FrequentFolders, is_visiable)
 }
 
+/// Sets the visibility of recent files and frequent folders together.
+///
+/// The registry key is opened once and Explorer is refreshed once, values passed as
+/// `None` are left untouched. Nothing is written when both are `None`.
+///
+/// # Arguments
+///
+/// * `recent` - Whether recent files should be visible, `None` to keep the current setting
+/// * `frequent` - Whether frequent folders should be visible, `None` to keep the current setting
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{visible::set_visibility, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     // Show frequent folders only
+///     set_visibility(Some(false), Some(true))?;
+///     Ok(())
+/// }
+/// ```
+pub fn set_visibility(recent: Option<bool>, frequent: Option<bool>) -> WincentResult<()> {
+    if recent.is_none() && frequent.is_none() {
+        return Ok(());
+    }
+
+    let reg_key = get_quick_access_reg()?;
+    check_fix_quick_acess_reg()?;
+
+    {
+        let _guard = lock_mutations();
+        for (value_name, visible) in [("ShowRecent", recent), ("ShowFrequent", frequent)] {
+            if let Some(visible) = visible {
+                reg_key
+                    .set_value(value_name, &u32::from(visible))
+                    .map_err(map_registry_write_error)?;
+            }
+        }
+    }
+
+    refresh_explorer_window()
+}
+
+/// Sets the visibility of a Quick Access section, refreshes Explorer and checks it applies.
+///
+/// Explorer shows recent files only while recent documents tracking is on, so showing
+/// them is reported as not in effect when the `NoRecentDocsHistory` policy or the
+/// "Show recently opened items" setting turns tracking off, see
+/// [`crate::feasible::is_recent_tracking_enabled`]. [`QuickAccess::All`] applies the
+/// visibility to both recent files and frequent folders.
+///
+/// # Arguments
+///
+/// * `target` - The section to show or hide
+/// * `visible` - Whether the section should be visible
+///
+/// # Returns
+///
+/// Returns `false` if the setting was written but Explorer will not show recent files
+/// anyway, `true` otherwise.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{visible::apply_visibility_and_refresh, QuickAccess, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     if !apply_visibility_and_refresh(QuickAccess::RecentFiles, true)? {
+///         println!("Recent files stay hidden, recent documents tracking is turned off");
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn apply_visibility_and_refresh(target: QuickAccess, visible: bool) -> WincentResult<bool> {
+    let (recent, frequent) = visibility_targets(target, visible);
+    set_visibility(recent, frequent)?;
+
+    // Tracking only matters when recent files should be shown
+    if recent != Some(true) {
+        return Ok(true);
+    }
+
+    is_recent_tracking_enabled()
+}
+
+/// Splits a target into the recent files and frequent folders settings to write.
+fn visibility_targets(target: QuickAccess, visible: bool) -> (Option<bool>, Option<bool>) {
+    match target {
+        QuickAccess::RecentFiles => (Some(visible), None),
+        QuickAccess::FrequentFolders => (None, Some(visible)),
+        QuickAccess::All => (Some(visible), Some(visible)),
+    }
+}
+
+/****************************************************** Recent Files Limit ******************************************************/
+
+const POLICIES_EXPLORER_KEY: &str =
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Policies\\Explorer";
+const MAX_RECENT_DOCS_VALUE: &str = "MaxRecentDocs";
+
+/// Range accepted by the "Maximum number of recent documents" policy.
+pub(crate) const RECENT_FILES_LIMIT_RANGE: std::ops::RangeInclusive<u32> = 1..=9999;
+
+/// Gets the maximum number of recent files Windows retains for the current user.
+///
+/// Reads the `MaxRecentDocs` value under
+/// `HKCU\Software\Microsoft\Windows\CurrentVersion\Policies\Explorer`, the setting
+/// behind the "Maximum number of recent documents" policy.
+///
+/// # Returns
+///
+/// Returns the limit, or `None` if it is not set and the Windows default applies.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{visible::get_recent_files_limit, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     match get_recent_files_limit()? {
+///         Some(limit) => println!("Keeping at most {} recent files", limit),
+///         None => println!("Using the Windows default"),
+///     }
+///     Ok(())
+/// }
+/// ```
+pub fn get_recent_files_limit() -> WincentResult<Option<u32>> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let key = match hkcu.open_subkey(POLICIES_EXPLORER_KEY) {
+        Ok(key) => key,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(WincentError::Io(e)),
+    };
+
+    match key.get_value::<u32, _>(MAX_RECENT_DOCS_VALUE) {
+        Ok(limit) => Ok(Some(limit)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(WincentError::Io(e)),
+    }
+}
+
+/// Sets the maximum number of recent files Windows retains for the current user.
+///
+/// Writes the `MaxRecentDocs` value described in [`get_recent_files_limit`]. Explorer
+/// picks up the new limit after a restart or sign-out. Pass `None` to remove the value
+/// and go back to the Windows default.
+///
+/// # Arguments
+///
+/// * `limit` - The new limit between 1 and 9999, or `None` for the default
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{visible::set_recent_files_limit, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     set_recent_files_limit(Some(20))?;
+///     Ok(())
+/// }
+/// ```
+pub fn set_recent_files_limit(limit: Option<u32>) -> WincentResult<()> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    if let Some(limit) = limit {
+        if !RECENT_FILES_LIMIT_RANGE.contains(&limit) {
+            return Err(WincentError::InvalidArgument(format!(
+                "Recent files limit must be between {} and {}: {}",
+                RECENT_FILES_LIMIT_RANGE.start(),
+                RECENT_FILES_LIMIT_RANGE.end(),
+                limit
+            )));
+        }
+    }
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let (key, _) = hkcu
+        .create_subkey(POLICIES_EXPLORER_KEY)
+        .map_err(WincentError::Io)?;
+
+    let _guard = lock_mutations();
+    match limit {
+        Some(limit) => key
+            .set_value(MAX_RECENT_DOCS_VALUE, &limit)
+            .map_err(WincentError::Io),
+        None => match key.delete_value(MAX_RECENT_DOCS_VALUE) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(WincentError::Io(e)),
+            _ => Ok(()),
+        },
+    }
+}
+
+/****************************************************** Suspend Display ******************************************************/
+
+/// Keeps Quick Access sections hidden until dropped, see [`suspend_display`].
+///
+/// Dropping the guard restores the prior visibility and refreshes Explorer once, ignoring
+/// errors. Call [`DisplayGuard::restore`] to handle them instead.
+#[derive(Debug)]
+#[must_use = "Quick Access is shown again as soon as the guard is dropped"]
+pub struct DisplayGuard {
+    recent_visible: bool,
+    frequent_visible: bool,
+    restored: bool,
+}
+
+impl DisplayGuard {
+    /// Restores the prior visibility and refreshes Explorer, reporting any error.
+    pub fn restore(mut self) -> WincentResult<()> {
+        self.restore_inner()
+    }
+
+    fn restore_inner(&mut self) -> WincentResult<()> {
+        if self.restored {
+            return Ok(());
+        }
+        self.restored = true;
+
+        set_visiable_with_registry(QuickAccess::RecentFiles, self.recent_visible)?;
+        set_visiable_with_registry(QuickAccess::FrequentFolders, self.frequent_visible)?;
+        refresh_explorer_window()
+    }
+}
+
+impl Drop for DisplayGuard {
+    fn drop(&mut self) {
+        let _ = self.restore_inner();
+    }
+}
+
+/// Hides recent files and frequent folders until the returned guard is dropped.
+///
+/// Useful during bulk operations, so Explorer does not redraw Quick Access for every
+/// added item. The prior `ShowRecent` and `ShowFrequent` values are kept in the guard,
+/// and Explorer is refreshed once so open windows stop showing the sections.
+///
+/// # Returns
+///
+/// Returns a guard restoring the prior visibility when dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use wincent::{handle::add_to_frequent_folders, visible::suspend_display, error::WincentError};
+///
+/// fn main() -> Result<(), WincentError> {
+///     let guard = suspend_display()?;
+///     for folder in ["C:\\Projects\\a", "C:\\Projects\\b"] {
+///         add_to_frequent_folders(folder)?;
+///     }
+///     guard.restore()?;
+///     Ok(())
+/// }
+/// ```
+pub fn suspend_display() -> WincentResult<DisplayGuard> {
+    let guard = DisplayGuard {
+        recent_visible: is_visialbe_with_registry(QuickAccess::RecentFiles)?,
+        frequent_visible: is_visialbe_with_registry(QuickAccess::FrequentFolders)?,
+        restored: false,
+    };
+
+    // On failure the guard is dropped and puts back whatever was already hidden
+    set_visiable_with_registry(QuickAccess::RecentFiles, false)?;
+    set_visiable_with_registry(QuickAccess::FrequentFolders, false)?;
+    refresh_explorer_window()?;
+
+    Ok(guard)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,4 +586,141 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[ignore]
+    fn test_set_visibility_partial() -> WincentResult<()> {
+        let initial_recent = is_visialbe_with_registry(QuickAccess::RecentFiles)?;
+        let initial_frequent = is_visialbe_with_registry(QuickAccess::FrequentFolders)?;
+
+        set_visibility(Some(!initial_recent), None)?;
+        assert_eq!(
+            is_visialbe_with_registry(QuickAccess::RecentFiles)?,
+            !initial_recent,
+            "Recent files visibility should be changed"
+        );
+        assert_eq!(
+            is_visialbe_with_registry(QuickAccess::FrequentFolders)?,
+            initial_frequent,
+            "Frequent folders visibility should be untouched"
+        );
+
+        set_visibility(Some(initial_recent), Some(initial_frequent))?;
+        assert_eq!(
+            is_visialbe_with_registry(QuickAccess::RecentFiles)?,
+            initial_recent,
+            "Should restore to initial state"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_visibility_targets() {
+        assert_eq!(
+            visibility_targets(QuickAccess::RecentFiles, true),
+            (Some(true), None)
+        );
+        assert_eq!(
+            visibility_targets(QuickAccess::FrequentFolders, false),
+            (None, Some(false))
+        );
+        assert_eq!(
+            visibility_targets(QuickAccess::All, false),
+            (Some(false), Some(false))
+        );
+    }
+
+    #[test]
+    #[ignore]
+    fn test_apply_visibility_and_refresh() -> WincentResult<()> {
+        let initial_recent = is_visialbe_with_registry(QuickAccess::RecentFiles)?;
+        let initial_frequent = is_visialbe_with_registry(QuickAccess::FrequentFolders)?;
+
+        assert_eq!(
+            apply_visibility_and_refresh(QuickAccess::RecentFiles, !initial_recent)?,
+            initial_recent || is_recent_tracking_enabled()?,
+            "Showing recent files should only apply while tracking is on"
+        );
+        assert_eq!(
+            is_visialbe_with_registry(QuickAccess::FrequentFolders)?,
+            initial_frequent,
+            "Frequent folders visibility should be untouched"
+        );
+
+        set_visibility(Some(initial_recent), Some(initial_frequent))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_map_registry_write_error() {
+        let denied = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied");
+        match map_registry_write_error(denied) {
+            WincentError::UnsupportedOperation(msg) => {
+                assert!(msg.contains("policy"), "Unexpected message: {}", msg)
+            }
+            other => panic!("Access denied should point to policy: {:?}", other),
+        }
+
+        let other = std::io::Error::other("other");
+        assert!(matches!(
+            map_registry_write_error(other),
+            WincentError::Io(_)
+        ));
+    }
+
+    #[test]
+    fn test_set_recent_files_limit_validates_range() {
+        for limit in [0, 10000] {
+            assert!(
+                matches!(
+                    set_recent_files_limit(Some(limit)),
+                    Err(WincentError::InvalidArgument(_))
+                ),
+                "Limit should be rejected: {}",
+                limit
+            );
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn test_recent_files_limit() -> WincentResult<()> {
+        let initial = get_recent_files_limit()?;
+
+        set_recent_files_limit(Some(42))?;
+        assert_eq!(get_recent_files_limit()?, Some(42));
+
+        set_recent_files_limit(None)?;
+        assert_eq!(get_recent_files_limit()?, None);
+
+        set_recent_files_limit(initial)?;
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn test_suspend_display() -> WincentResult<()> {
+        let initial_recent = is_visialbe_with_registry(QuickAccess::RecentFiles)?;
+        let initial_frequent = is_visialbe_with_registry(QuickAccess::FrequentFolders)?;
+
+        {
+            let _guard = suspend_display()?;
+            assert!(!is_visialbe_with_registry(QuickAccess::RecentFiles)?);
+            assert!(!is_visialbe_with_registry(QuickAccess::FrequentFolders)?);
+        }
+
+        assert_eq!(
+            is_visialbe_with_registry(QuickAccess::RecentFiles)?,
+            initial_recent,
+            "Recent files visibility should be restored on drop"
+        );
+        assert_eq!(
+            is_visialbe_with_registry(QuickAccess::FrequentFolders)?,
+            initial_frequent,
+            "Frequent folders visibility should be restored on drop"
+        );
+
+        Ok(())
+    }
 }
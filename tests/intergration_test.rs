@@ -4,7 +4,7 @@ mod tests {
     use std::io::Write;
     use std::path::{Path, PathBuf};
     use std::{thread, time::Duration};
-    use wincent::predule::*;
+    use wincent::prelude::*;
 
     /// Create test environment
     pub(crate) fn setup_test_env() -> WincentResult<PathBuf> {
@@ -33,6 +33,15 @@ mod tests {
         Ok(file_path)
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_prelude_alias() {
+        use wincent::predule::QuickAccess as LegacyQuickAccess;
+
+        let qa_type: QuickAccess = LegacyQuickAccess::RecentFiles;
+        assert_eq!(qa_type, QuickAccess::RecentFiles);
+    }
+
     #[test_log::test]
     #[ignore]
     fn test_feasibility_checks() -> WincentResult<()> {